@@ -9,19 +9,45 @@ main() {
 
     local examples=(
         _llseek
+        atomic-file
+        canonicalize
+        copy
         create
+        dir
         dup
+        epoll
+        eventfd
         format
         format
         hello
+        inotify
         instant
+        io-uring
+        lock
         ls
+        memfd
+        mmap
+        ns
         open
+        poll
         preadwrite
+        prctl
+        pty
+        read-write
+        resource
+        seccomp
+        sparse
+        splice
         stat
         stderr
         system-time
+        termios
+        timerfd
+        times
         vec
+        vectored
+        walk-dir
+        xattr
         zero
     )
 
@@ -0,0 +1,41 @@
+#![feature(atomic_file)]
+
+use std::fs::{self, AtomicFile, File};
+use std::io::{Read, Write};
+use std::process;
+
+fn contents(path: &str) -> String {
+    let mut s = String::new();
+    File::open(path).unwrap().read_to_string(&mut s).unwrap();
+    s
+}
+
+pub fn main() {
+    let path = "/target/atomic-file";
+    File::create(path).unwrap().write_all(b"old").unwrap();
+
+    let mut f = AtomicFile::new(path).unwrap();
+    f.write_all(b"new").unwrap();
+    // Nothing changes until the file is committed
+    if contents(path) != "old" {
+        process::exit(1);
+    }
+    f.commit().unwrap();
+    if contents(path) != "new" {
+        process::exit(1);
+    }
+
+    // Dropping an uncommitted file leaves no trace behind
+    let entries = fs::read_dir("/target").unwrap().count();
+    AtomicFile::new(path).unwrap().write_all(b"lost").unwrap();
+    if contents(path) != "new" ||
+       fs::read_dir("/target").unwrap().count() != entries {
+        process::exit(1);
+    }
+
+    let mut f = AtomicFile::new(path).unwrap();
+    f.write_all(b"other").unwrap();
+    if f.commit_new().is_ok() || contents(path) != "new" {
+        process::exit(1);
+    }
+}
@@ -1,5 +1,53 @@
+#![feature(ns)]
+
 use std::fs;
+use std::os::linux::ns::{self, IdMap};
+use std::os::unix::fs::{symlink, MetadataExt};
+use std::path::Path;
+use std::process;
+
+pub fn main() {
+    let root = fs::canonicalize("/target").unwrap();
+    fs::remove_dir_all("/target/canonicalize").ok();
+    fs::create_dir_all("/target/canonicalize/a/b").unwrap();
+    symlink("a/b", "/target/canonicalize/link").unwrap();
+    symlink("loop", "/target/canonicalize/loop").unwrap();
+
+    check(&root);
+
+    // Without /proc, canonicalize resolves the path in userspace
+    let uid = fs::metadata("/proc/self").unwrap().uid();
+    match ns::unshare(ns::CLONE_NEWUSER | ns::CLONE_NEWNS) {
+        Ok(()) => {}
+        // Containers, emulators and kernels without user namespaces
+        Err(ref e) if e.raw_os_error() == Some(1) ||
+                      e.raw_os_error() == Some(22) ||
+                      e.raw_os_error() == Some(38) => {
+            println!("user namespaces unavailable: {}", e);
+            return;
+        }
+        Err(e) => panic!("{}", e),
+    }
+    ns::write_uid_map(0, &[IdMap { inside: 0, outside: uid, count: 1 }])
+        .unwrap();
+    ns::mount("none", "/", None, ns::MS_REC | ns::MS_PRIVATE, None).unwrap();
+    ns::mount("tmpfs", "/proc", Some("tmpfs"), 0, Some("size=1m")).unwrap();
+    if fs::metadata("/proc/self").is_ok() {
+        process::exit(1);
+    }
+
+    check(&root);
+}
+
+fn check(root: &Path) {
+    let p = fs::canonicalize("/target/canonicalize/link/../b/.").unwrap();
+    println!("{}", p.display());
+    if p != root.join("canonicalize/a/b") {
+        process::exit(1);
+    }
 
-fn main() {
-    println!("{}", fs::canonicalize(".").unwrap().display());
+    if fs::canonicalize("/target/canonicalize/loop").is_ok() ||
+       fs::canonicalize("/target/canonicalize/missing").is_ok() {
+        process::exit(1);
+    }
 }
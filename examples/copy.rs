@@ -0,0 +1,26 @@
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::process;
+
+pub fn main() {
+    let mut contents = vec![];
+    for i in 0..100_000u32 {
+        contents.push((i % 251) as u8);
+    }
+
+    let mut f = File::create("/target/copy-from").unwrap();
+    f.write_all(&contents).unwrap();
+    fs::set_permissions("/target/copy-from",
+                        fs::Permissions::from_mode(0o640)).unwrap();
+
+    let n = fs::copy("/target/copy-from", "/target/copy-to").unwrap();
+
+    let mut copied = vec![];
+    File::open("/target/copy-to").unwrap().read_to_end(&mut copied).unwrap();
+    let mode = fs::metadata("/target/copy-to").unwrap().permissions().mode();
+    println!("{} {:o}", n, mode & 0o777);
+    if n != contents.len() as u64 || copied != contents || mode & 0o777 != 0o640 {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,44 @@
+#![feature(dir_fd)]
+
+use std::ffi::OsString;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::os::linux::fs::Dir;
+use std::path::Path;
+use std::process;
+
+pub fn main() {
+    let _ = fs::remove_dir_all("/target/dir");
+    fs::create_dir("/target/dir").unwrap();
+
+    let dir = Dir::open("/target/dir").unwrap();
+    dir.mkdir_at("sub", 0o755).unwrap();
+    let sub = dir.open_dir_at("sub").unwrap();
+    let mut f = sub.open_at("file", OpenOptions::new().write(true).create(true))
+        .unwrap();
+    f.write_all(b"Hello, world!\n").unwrap();
+    sub.symlink_at("file", "link").unwrap();
+    sub.rename_at("file", &dir, "moved", 0).unwrap();
+
+    if sub.open_dir_at("link").is_ok() ||
+       !sub.lstat_at("link").unwrap().file_type().is_symlink() ||
+       dir.stat_at("moved").unwrap().len() != 14 {
+        process::exit(1);
+    }
+
+    let mut names = vec![];
+    for entry in dir.read_dir().unwrap() {
+        let entry = entry.unwrap();
+        println!("{}", entry.path().display());
+        names.push(entry.file_name());
+    }
+    names.sort();
+    if names != [OsString::from("moved"), OsString::from("sub")] {
+        process::exit(1);
+    }
+
+    fs::remove_dir_all("/target/dir").unwrap();
+    if Path::new("/target/dir").exists() {
+        process::exit(1);
+    }
+}
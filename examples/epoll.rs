@@ -0,0 +1,75 @@
+#![feature(epoll)]
+#![feature(splice)]
+
+use std::io::{Read, Write};
+use std::os::linux::epoll::{Epoll, Events, EPOLLET, EPOLLHUP, EPOLLIN,
+                            EPOLLONESHOT};
+use std::os::linux::io::pipe;
+use std::process;
+use std::time::Duration;
+
+fn wait(epoll: &Epoll, events: &mut Events) -> usize {
+    epoll.wait(events, Some(Duration::from_millis(10))).unwrap()
+}
+
+pub fn main() {
+    let (mut rx, mut tx) = pipe().unwrap();
+    let epoll = Epoll::new().unwrap();
+    let mut events = Events::with_capacity(4);
+
+    // Level-triggered
+    epoll.add(&rx, EPOLLIN, 7).unwrap();
+    if wait(&epoll, &mut events) != 0 {
+        process::exit(1);
+    }
+    tx.write_all(b"ab").unwrap();
+    for _ in 0..2 {
+        if wait(&epoll, &mut events) != 1 {
+            process::exit(1);
+        }
+        let event = events.iter().next().unwrap();
+        if event.token != 7 || event.events != EPOLLIN {
+            process::exit(1);
+        }
+    }
+
+    // One-shot, until re-armed
+    epoll.modify(&rx, EPOLLIN | EPOLLONESHOT, 8).unwrap();
+    if wait(&epoll, &mut events) != 1 || wait(&epoll, &mut events) != 0 {
+        process::exit(1);
+    }
+    epoll.modify(&rx, EPOLLIN | EPOLLONESHOT, 9).unwrap();
+    if wait(&epoll, &mut events) != 1 ||
+       events.iter().next().unwrap().token != 9 {
+        process::exit(1);
+    }
+
+    // Edge-triggered, only reported again once more data arrives
+    epoll.modify(&rx, EPOLLIN | EPOLLET, 10).unwrap();
+    if wait(&epoll, &mut events) != 1 || wait(&epoll, &mut events) != 0 {
+        process::exit(1);
+    }
+    tx.write_all(b"c").unwrap();
+    if wait(&epoll, &mut events) != 1 {
+        process::exit(1);
+    }
+    let mut buf = [0; 3];
+    rx.read_exact(&mut buf).unwrap();
+
+    // Hang-up is reported even though it wasn't asked for
+    epoll.modify(&rx, EPOLLIN, 11).unwrap();
+    drop(tx);
+    if wait(&epoll, &mut events) != 1 {
+        process::exit(1);
+    }
+    let event = events.iter().next().unwrap();
+    println!("{:?}", event);
+    if event.events & EPOLLHUP == 0 {
+        process::exit(1);
+    }
+
+    epoll.delete(&rx).unwrap();
+    if wait(&epoll, &mut events) != 0 || !events.is_empty() {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,36 @@
+#![feature(eventfd)]
+
+use std::io::ErrorKind;
+use std::os::linux::EventFd;
+use std::process;
+
+pub fn main() {
+    // Counter mode, a read takes everything
+    let counter = EventFd::new(1).unwrap();
+    counter.write(2).unwrap();
+    counter.write(3).unwrap();
+    let n = counter.read().unwrap();
+    println!("{}", n);
+    if n != 6 {
+        process::exit(1);
+    }
+    counter.set_nonblocking(true).unwrap();
+    match counter.read() {
+        Err(ref e) if e.kind() == ErrorKind::WouldBlock => {}
+        _ => process::exit(1),
+    }
+    if counter.write(u64::max_value()).is_ok() {
+        process::exit(1);
+    }
+
+    // Semaphore mode, a read takes one
+    let semaphore = EventFd::semaphore(2).unwrap();
+    semaphore.set_nonblocking(true).unwrap();
+    if semaphore.read().unwrap() != 1 || semaphore.read().unwrap() != 1 {
+        process::exit(1);
+    }
+    match semaphore.read() {
+        Err(ref e) if e.kind() == ErrorKind::WouldBlock => {}
+        _ => process::exit(1),
+    }
+}
@@ -0,0 +1,46 @@
+#![feature(inotify)]
+
+use std::fs::{self, File};
+use std::io::ErrorKind;
+use std::os::linux::inotify::{Inotify, IN_CLOSE_WRITE, IN_CREATE, IN_MOVED_FROM,
+                              IN_MOVED_TO};
+use std::process;
+
+pub fn main() {
+    let dir = "/target/inotify";
+    fs::create_dir(dir).ok();
+
+    let inotify = Inotify::new().unwrap();
+    inotify.set_nonblocking(true).unwrap();
+    let wd = inotify
+        .add_watch(dir, IN_CREATE | IN_CLOSE_WRITE | IN_MOVED_FROM | IN_MOVED_TO)
+        .unwrap();
+
+    let mut buffer = [0; 4096];
+    match inotify.read_events(&mut buffer) {
+        Err(ref e) if e.kind() == ErrorKind::WouldBlock => {}
+        _ => process::exit(1),
+    }
+
+    File::create("/target/inotify/a").unwrap();
+    fs::rename("/target/inotify/a", "/target/inotify/b").unwrap();
+
+    let events = inotify.read_events(&mut buffer).unwrap().collect::<Vec<_>>();
+    println!("{:?}", events);
+    let expected = [(IN_CREATE, "a"), (IN_CLOSE_WRITE, "a"), (IN_MOVED_FROM, "a"),
+                    (IN_MOVED_TO, "b")];
+    if events.len() != expected.len() {
+        process::exit(1);
+    }
+    for (event, &(mask, name)) in events.iter().zip(expected.iter()) {
+        if event.wd != wd || event.mask != mask || event.name.unwrap() != name {
+            process::exit(1);
+        }
+    }
+    if events[2].cookie == 0 || events[2].cookie != events[3].cookie {
+        process::exit(1);
+    }
+
+    inotify.rm_watch(wd).unwrap();
+    fs::remove_file("/target/inotify/b").unwrap();
+}
@@ -0,0 +1,90 @@
+#![feature(io_uring)]
+#![feature(splice)]
+
+use std::fs::OpenOptions;
+use std::os::linux::io::pipe;
+use std::os::linux::io_uring::{Completion, IoUring, Outcome};
+use std::process;
+
+fn complete(ring: &mut IoUring, n: usize) -> Vec<Completion> {
+    ring.wait(n).unwrap();
+    let completions = ring.completions().collect::<Vec<_>>();
+    if completions.len() < n {
+        process::exit(1);
+    }
+    completions
+}
+
+pub fn main() {
+    let mut ring = match IoUring::new(4) {
+        Ok(ring) => ring,
+        // Older kernels, emulators and sandboxes
+        Err(ref e) if e.raw_os_error() == Some(38) ||
+                      e.raw_os_error() == Some(1) => {
+            println!("io_uring unavailable: {}", e);
+            return;
+        }
+        Err(e) => panic!("{}", e),
+    };
+    println!("{:?}", ring);
+
+    let path = "/target/io-uring";
+    let mut opts = OpenOptions::new();
+    opts.read(true).write(true).create(true).truncate(true);
+    let token = ring.openat(None, path, &opts).unwrap();
+    let mut completions = complete(&mut ring, 1);
+    let file = match completions.pop().unwrap() {
+        Completion { token: t, outcome: Outcome::Openat(Ok(file)) } => {
+            if t != token {
+                process::exit(1);
+            }
+            file
+        }
+        _ => process::exit(1),
+    };
+
+    ring.write(&file, b"Hello, world!".to_vec(), 0).unwrap();
+    match complete(&mut ring, 1).pop().unwrap().outcome {
+        Outcome::Write(Ok(13), ref buf) if buf == b"Hello, world!" => {}
+        _ => process::exit(1),
+    }
+
+    ring.fsync(&file, true).unwrap();
+    ring.statx(None, path).unwrap();
+    ring.read(&file, vec![0; 64], 7).unwrap();
+    let completions = complete(&mut ring, 3);
+    println!("{:?}", completions);
+    for completion in completions {
+        match completion.outcome {
+            Outcome::Fsync(Ok(())) => {}
+            Outcome::Statx(Ok(ref meta)) if meta.len() == 13 &&
+                                            meta.is_file() => {}
+            Outcome::Read(Ok(6), ref buf) if buf == b"world!" => {}
+            _ => process::exit(1),
+        }
+    }
+
+    ring.close(file).unwrap();
+    match complete(&mut ring, 1).pop().unwrap().outcome {
+        Outcome::Close(Ok(())) => {}
+        _ => process::exit(1),
+    }
+
+    // Errors are reported per operation
+    ring.statx(None, "/target/io-uring-missing").unwrap();
+    match complete(&mut ring, 1).pop().unwrap().outcome {
+        Outcome::Statx(Err(ref e)) if e.raw_os_error() == Some(2) => {}
+        _ => process::exit(1),
+    }
+
+    // Dropping waits for operations in flight, and cancels a read of a pipe
+    // that nothing is ever written to
+    let file = std::fs::File::open(path).unwrap();
+    let (rx, _tx) = pipe().unwrap();
+    ring.read(&file, vec![0; 64], 0).unwrap();
+    ring.read(&rx, vec![0; 64], u64::max_value()).unwrap();
+    if ring.in_flight() != 2 {
+        process::exit(1);
+    }
+    drop(ring);
+}
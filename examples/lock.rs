@@ -0,0 +1,36 @@
+#![feature(file_lock)]
+#![feature(linux_file_ext)]
+
+use std::fs::{File, OpenOptions};
+use std::os::linux::fs::FileExt;
+use std::process;
+
+pub fn main() {
+    let path = "/target/lock";
+    let a = File::create(path).unwrap();
+    let b = OpenOptions::new().read(true).write(true).open(path).unwrap();
+
+    a.lock_exclusive().unwrap();
+    if b.try_lock_shared().unwrap() {
+        process::exit(1);
+    }
+    a.unlock().unwrap();
+    if !b.try_lock_shared().unwrap() || !a.try_lock_shared().unwrap() {
+        process::exit(1);
+    }
+    a.unlock().unwrap();
+    b.unlock().unwrap();
+
+    // Byte range locks of different open files conflict even within the same
+    // process, but only where the ranges overlap.
+    a.lock_range_exclusive(0, 10).unwrap();
+    if b.try_lock_range_shared(5, 10).unwrap() ||
+       !b.try_lock_range_exclusive(10, 0).unwrap() {
+        process::exit(1);
+    }
+    a.unlock_range(0, 10).unwrap();
+    if !b.try_lock_range_shared(0, 10).unwrap() {
+        process::exit(1);
+    }
+    println!("ok");
+}
@@ -0,0 +1,38 @@
+#![feature(memfd)]
+#![feature(linux_file_ext)]
+
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::os::linux::fs::{FileExt, MemFd, F_SEAL_GROW, F_SEAL_SEAL,
+                         F_SEAL_SHRINK, F_SEAL_WRITE};
+use std::process;
+
+pub fn main() {
+    let mut f = MemFd::new().allow_sealing(true).create("steed").unwrap();
+    f.write_all(b"Hello, world!").unwrap();
+    if f.seals().unwrap() != 0 {
+        process::exit(1);
+    }
+
+    f.add_seals(F_SEAL_SHRINK | F_SEAL_GROW | F_SEAL_WRITE).unwrap();
+    if f.write_all(b"more").is_ok() || f.set_len(0).is_ok() {
+        process::exit(1);
+    }
+
+    let mut s = String::new();
+    f.seek(SeekFrom::Start(0)).unwrap();
+    f.read_to_string(&mut s).unwrap();
+    if s != "Hello, world!" {
+        process::exit(1);
+    }
+
+    f.add_seals(F_SEAL_SEAL).unwrap();
+    if f.add_seals(F_SEAL_SHRINK).is_ok() {
+        process::exit(1);
+    }
+
+    // Without sealing allowed the file starts out with F_SEAL_SEAL
+    let f = MemFd::new().create("steed").unwrap();
+    if f.seals().unwrap() != F_SEAL_SEAL {
+        process::exit(1);
+    }
+}
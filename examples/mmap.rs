@@ -0,0 +1,56 @@
+#![feature(mmap)]
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::os::linux::mmap::{Advice, Mmap, MmapMut};
+use std::process;
+
+pub fn main() {
+    let path = "/target/mmap";
+    let mut f = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)
+        .unwrap();
+    f.write_all(&[0; 8192]).unwrap();
+    f.write_all(b"Hello, world!").unwrap();
+
+    // An offset that isn't page aligned
+    let map = Mmap::map_range(&f, 8192 + 7, 6).unwrap();
+    map.advise(Advice::Sequential).unwrap();
+    if &map[..] != b"world!" {
+        process::exit(1);
+    }
+
+    // Writes through a shared map are visible through every map of the file
+    let mut map_mut = MmapMut::map_mut(&f).unwrap();
+    map_mut[8192 + 7..8192 + 12].copy_from_slice(b"steed");
+    map_mut.flush().unwrap();
+    if &map[..] != b"steed!" {
+        process::exit(1);
+    }
+
+    // Maps can't extend past the end of the file...
+    if Mmap::map_range(&f, 8192, 4096).is_ok() {
+        process::exit(1);
+    }
+
+    // ... and shrinking the file afterwards is caught by `check`
+    map.check().unwrap();
+    f.set_len(8192).unwrap();
+    if map.check().is_ok() || map_mut.check().is_ok() {
+        process::exit(1);
+    }
+
+    let mut anon = MmapMut::map_anon(4096).unwrap();
+    if anon.iter().any(|&b| b != 0) {
+        process::exit(1);
+    }
+    anon[4095] = 42;
+    anon.lock().ok();
+    if anon[4095] != 42 {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,64 @@
+#![feature(ns)]
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::os::linux::ns::{self, IdMap};
+use std::os::unix::fs::MetadataExt;
+use std::process;
+
+pub fn main() {
+    let meta = fs::metadata("/proc/self").unwrap();
+    let (uid, gid) = (meta.uid(), meta.gid());
+
+    match ns::unshare(ns::CLONE_NEWUSER | ns::CLONE_NEWNS) {
+        Ok(()) => {}
+        // Containers, emulators and kernels without user namespaces
+        Err(ref e) if e.raw_os_error() == Some(1) ||
+                      e.raw_os_error() == Some(22) ||
+                      e.raw_os_error() == Some(38) => {
+            println!("user namespaces unavailable: {}", e);
+            return;
+        }
+        Err(e) => panic!("{}", e),
+    }
+    ns::write_uid_map(0, &[IdMap { inside: 0, outside: uid, count: 1 }])
+        .unwrap();
+    ns::deny_setgroups(0).unwrap();
+    ns::write_gid_map(0, &[IdMap { inside: 0, outside: gid, count: 1 }])
+        .unwrap();
+    // The maps can only be written once
+    if ns::write_uid_map(0, &[IdMap { inside: 0, outside: uid, count: 1 }])
+        .is_ok() {
+        process::exit(1);
+    }
+    let meta = fs::metadata("/proc/self").unwrap();
+    if meta.uid() != 0 || meta.gid() != 0 {
+        process::exit(1);
+    }
+
+    let root = "/target/ns";
+    let _ = fs::create_dir(root);
+    ns::mount("none", "/", None, ns::MS_REC | ns::MS_PRIVATE, None).unwrap();
+    ns::mount("tmpfs", root, Some("tmpfs"), 0, Some("size=1m")).unwrap();
+    fs::create_dir("/target/ns/old").unwrap();
+    fs::create_dir("/target/ns/inner").unwrap();
+    File::create("/target/ns/inner/marker")
+        .unwrap()
+        .write_all(b"Hello, world!")
+        .unwrap();
+
+    ns::pivot_root(root, "/target/ns/old").unwrap();
+    if fs::metadata("/old/target/ns/inner/marker").is_ok() ||
+       fs::metadata("/inner/marker").unwrap().len() != 13 {
+        process::exit(1);
+    }
+    ns::umount2("/old", ns::MNT_DETACH).unwrap();
+    if fs::read_dir("/old").unwrap().count() != 0 {
+        process::exit(1);
+    }
+
+    ns::chroot("/inner").unwrap();
+    if fs::metadata("/marker").unwrap().len() != 13 {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,37 @@
+#![feature(poll)]
+#![feature(splice)]
+
+use std::io::Write;
+use std::os::linux::io::pipe;
+use std::os::unix::io::{poll, AsRawFd, PollFd, POLLHUP, POLLIN, POLLOUT};
+use std::process;
+use std::time::{Duration, Instant};
+
+pub fn main() {
+    let (rx, mut tx) = pipe().unwrap();
+
+    let mut fds = [PollFd::new(rx.as_raw_fd(), POLLIN), PollFd::new(-1, POLLIN)];
+    let start = Instant::now();
+    let n = poll(&mut fds, Some(Duration::from_millis(20))).unwrap();
+    if n != 0 || start.elapsed() < Duration::from_millis(20) {
+        process::exit(1);
+    }
+
+    tx.write_all(b"x").unwrap();
+    let mut fds = [PollFd::new(rx.as_raw_fd(), POLLIN),
+                   PollFd::new(tx.as_raw_fd(), POLLOUT),
+                   PollFd::new(-1, POLLIN)];
+    let n = poll(&mut fds, None).unwrap();
+    println!("{:?}", fds);
+    if n != 2 || fds[0].revents() != POLLIN || fds[1].revents() != POLLOUT ||
+       fds[2].revents() != 0 {
+        process::exit(1);
+    }
+
+    drop(tx);
+    let mut fds = [PollFd::new(rx.as_raw_fd(), POLLIN)];
+    let n = poll(&mut fds, Some(Duration::new(0, 0))).unwrap();
+    if n != 1 || fds[0].revents() & POLLHUP == 0 {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,57 @@
+#![feature(linux_process_ext)]
+
+use std::os::linux::process as linux;
+use std::process;
+use std::time::Duration;
+
+pub fn main() {
+    linux::set_name("steed-prctl").unwrap();
+    if linux::name().unwrap().to_str() != Some("steed-prctl") {
+        process::exit(1);
+    }
+    // Truncated to 15 bytes
+    linux::set_name("a-rather-long-thread-name").unwrap();
+    if linux::name().unwrap().to_str() != Some("a-rather-long-t") {
+        process::exit(1);
+    }
+    if linux::set_name("nul\0byte").is_ok() {
+        process::exit(1);
+    }
+
+    // SIGTERM
+    linux::set_parent_death_signal(Some(15)).unwrap();
+    if linux::parent_death_signal().unwrap() != Some(15) {
+        process::exit(1);
+    }
+    linux::set_parent_death_signal(None).unwrap();
+    if linux::parent_death_signal().unwrap() != None {
+        process::exit(1);
+    }
+
+    linux::set_dumpable(false).unwrap();
+    if linux::dumpable().unwrap() {
+        process::exit(1);
+    }
+    linux::set_dumpable(true).unwrap();
+    if !linux::dumpable().unwrap() {
+        process::exit(1);
+    }
+
+    linux::set_child_subreaper(true).unwrap();
+    if !linux::child_subreaper().unwrap() {
+        process::exit(1);
+    }
+    linux::set_child_subreaper(false).unwrap();
+
+    linux::set_timer_slack(Duration::new(0, 200_000)).unwrap();
+    if linux::timer_slack().unwrap() != Duration::new(0, 200_000) {
+        process::exit(1);
+    }
+
+    // Last, as it can't be undone
+    linux::set_no_new_privs().unwrap();
+    if !linux::no_new_privs().unwrap() {
+        process::exit(1);
+    }
+    println!("{:?}", linux::name().unwrap());
+}
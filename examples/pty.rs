@@ -0,0 +1,64 @@
+#![feature(is_terminal)]
+#![feature(pty)]
+#![feature(termios)]
+
+use std::io::{Read, Write};
+use std::os::linux::pty::{self, openpty};
+use std::os::linux::termios::{self, Termios, WindowSize};
+use std::os::unix::io::IsTerminal;
+use std::process;
+
+pub fn main() {
+    let size = WindowSize { rows: 24, cols: 80, x_pixels: 0, y_pixels: 0 };
+    let mut pty = openpty(Some(size)).unwrap();
+    println!("{:?} {:?}", pty, pty::ptsname(&pty.master).unwrap());
+    if !pty.slave.is_terminal() ||
+       termios::window_size(&pty.slave).unwrap() != size {
+        process::exit(1);
+    }
+    if !pty::ptsname(&pty.master).unwrap().starts_with("/dev/pts") {
+        process::exit(1);
+    }
+
+    // Canonical mode with echo: input arrives by line and is echoed back,
+    // output newlines are translated
+    pty.master.write_all(b"hi\n").unwrap();
+    let mut buf = [0; 3];
+    pty.slave.read_exact(&mut buf).unwrap();
+    if &buf != b"hi\n" {
+        process::exit(1);
+    }
+    let mut buf = [0; 4];
+    pty.master.read_exact(&mut buf).unwrap();
+    if &buf != b"hi\r\n" {
+        process::exit(1);
+    }
+
+    // Raw mode: bytes pass through unchanged
+    {
+        let _guard = termios::raw_mode(&pty.slave).unwrap();
+        let raw = Termios::get(&pty.slave).unwrap();
+        if raw.echo() || raw.canonical() {
+            process::exit(1);
+        }
+        pty.master.write_all(b"abc").unwrap();
+        let mut buf = [0; 3];
+        (&pty.slave).read_exact(&mut buf).unwrap();
+        (&pty.slave).write_all(b"x\n").unwrap();
+        let mut out = [0; 2];
+        pty.master.read_exact(&mut out).unwrap();
+        if &buf != b"abc" || &out != b"x\n" {
+            process::exit(1);
+        }
+    }
+    let restored = Termios::get(&pty.slave).unwrap();
+    if !restored.echo() || !restored.canonical() {
+        process::exit(1);
+    }
+
+    let size = WindowSize { rows: 50, cols: 132, x_pixels: 0, y_pixels: 0 };
+    termios::set_window_size(&pty.master, size).unwrap();
+    if termios::window_size(&pty.slave).unwrap() != size {
+        process::exit(1);
+    }
+}
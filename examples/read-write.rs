@@ -0,0 +1,33 @@
+#![feature(fs_read_write)]
+
+use std::fs;
+use std::process;
+
+pub fn main() {
+    let path = "/target/read-write";
+    fs::write(path, "Hello, world!").unwrap();
+    if fs::read(path).unwrap() != b"Hello, world!" ||
+       fs::read_to_string(path).unwrap() != "Hello, world!" {
+        process::exit(1);
+    }
+
+    let big = vec![42; 100_000];
+    fs::write(path, &big).unwrap();
+    let bytes = fs::read(path).unwrap();
+    // Sized from the metadata, the buffer never had to grow
+    if bytes != big || bytes.capacity() != big.len() + 1 {
+        process::exit(1);
+    }
+
+    // procfs files report a size of zero
+    if let Ok(status) = fs::read_to_string("/proc/self/status") {
+        if !status.starts_with("Name:") {
+            process::exit(1);
+        }
+    }
+
+    fs::write(path, [0xff]).unwrap();
+    if fs::read_to_string(path).is_ok() {
+        process::exit(1);
+    }
+}
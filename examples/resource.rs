@@ -0,0 +1,65 @@
+#![feature(resource)]
+
+use std::fs::File;
+use std::os::linux::resource::{self, Limit, Resource, Who};
+use std::process;
+use std::time::{Duration, Instant};
+
+pub fn main() {
+    let saved = resource::get_limit(Resource::Nofile).unwrap();
+    println!("{:?}", saved);
+
+    // Opening runs out of descriptors at the soft limit
+    let low = Limit { soft: Some(64), hard: saved.hard };
+    resource::set_limit(Resource::Nofile, low).unwrap();
+    if resource::get_limit(Resource::Nofile).unwrap() != low {
+        process::exit(1);
+    }
+    let mut files = Vec::new();
+    loop {
+        match File::open("/dev/null") {
+            Ok(file) => files.push(file),
+            // EMFILE
+            Err(ref e) if e.raw_os_error() == Some(24) => break,
+            Err(e) => panic!("{}", e),
+        }
+        if files.len() == 64 {
+            process::exit(1);
+        }
+    }
+    drop(files);
+    if resource::prlimit(0, Resource::Nofile, Some(saved)).unwrap() != low {
+        process::exit(1);
+    }
+
+    // EINVAL, soft above hard
+    if let Some(hard) = saved.hard {
+        let bad = Limit { soft: None, hard: Some(hard) };
+        match resource::set_limit(Resource::Nofile, bad) {
+            Err(ref e) if e.raw_os_error() == Some(22) => {}
+            _ => process::exit(1),
+        }
+    }
+
+    // Burn some CPU until it shows up
+    let start = Instant::now();
+    let mut x = 0u64;
+    loop {
+        for i in 0..100000 {
+            x = x.wrapping_mul(31).wrapping_add(i);
+        }
+        let usage = resource::getrusage(Who::Process).unwrap();
+        if usage.user_time + usage.system_time > Duration::new(0, 0) {
+            println!("{:?} {}", usage, x);
+            break;
+        }
+        if start.elapsed() > Duration::from_secs(10) {
+            process::exit(1);
+        }
+    }
+    resource::getrusage(Who::Thread).unwrap();
+    let children = resource::getrusage(Who::Children).unwrap();
+    if children.user_time != Duration::new(0, 0) {
+        process::exit(1);
+    }
+}
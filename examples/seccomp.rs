@@ -0,0 +1,55 @@
+#![feature(resource)]
+#![feature(seccomp)]
+
+use std::os::linux::resource::{self, Resource};
+use std::os::linux::seccomp::{nr, Action, Arg, Cmp, Filter};
+use std::process;
+
+fn errno(resource: Resource) -> Option<i32> {
+    resource::get_limit(resource).err().and_then(|e| e.raw_os_error())
+}
+
+pub fn main() {
+    let which = 1;
+    let mut filter = Filter::new(Action::Allow);
+    filter.rule(nr::PRLIMIT64, &[Arg::new(0, Cmp::Ne, 0)], Action::Errno(3))
+        .rule(nr::PRLIMIT64,
+              &[Arg::new(which, Cmp::Eq, Resource::Nofile as u64)],
+              Action::Errno(13))
+        .rule(nr::PRLIMIT64,
+              &[Arg::new(which, Cmp::MaskedEq(!0), Resource::Core as u64)],
+              Action::Errno(1))
+        // CPU and FSIZE, which are 0 and 1 everywhere
+        .rule(nr::PRLIMIT64,
+              &[Arg::new(which, Cmp::Ge, 0), Arg::new(which, Cmp::Lt, 2)],
+              Action::Errno(22))
+        .deny(nr::GETRUSAGE);
+    println!("{:?}", filter);
+
+    match filter.install() {
+        Ok(()) => {}
+        // Emulators and kernels without seccomp. EINVAL is left to fail, as
+        // it's also what a malformed filter gets.
+        Err(ref e) if e.raw_os_error() == Some(38) => {
+            println!("seccomp unavailable: {}", e);
+            return;
+        }
+        Err(e) => panic!("{}", e),
+    }
+
+    if errno(Resource::Nofile) != Some(13) ||
+       errno(Resource::Core) != Some(1) ||
+       errno(Resource::Cpu) != Some(22) ||
+       errno(Resource::Fsize) != Some(22) ||
+       errno(Resource::Data).is_some() ||
+       errno(Resource::Stack).is_some() {
+        process::exit(1);
+    }
+    match resource::prlimit(1, Resource::Stack, None) {
+        Err(ref e) if e.raw_os_error() == Some(3) => {}
+        _ => process::exit(1),
+    }
+    if resource::getrusage(resource::Who::Process).is_ok() {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,47 @@
+#![feature(linux_file_ext)]
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::os::linux::fs::FileExt;
+use std::process;
+
+const MB: u64 = 1024 * 1024;
+
+pub fn main() {
+    let path = "/target/sparse";
+    let mut f = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)
+        .unwrap();
+
+    // 4 MiB of data with a 2 MiB hole in the middle
+    f.allocate(0, 4 * MB).unwrap();
+    f.write_all(b"head").unwrap();
+    f.seek(SeekFrom::Start(3 * MB)).unwrap();
+    f.write_all(b"tail").unwrap();
+    if f.punch_hole(MB, 2 * MB).is_err() {
+        // The filesystem doesn't do holes, nothing else to test
+        return;
+    }
+    if f.metadata().unwrap().len() != 4 * MB {
+        process::exit(1);
+    }
+
+    let regions = f.data_regions().map(|r| r.unwrap()).collect::<Vec<_>>();
+    println!("{:?}", regions);
+    if regions.len() != 2 || regions[0].end > MB || regions[1].start < 3 * MB {
+        process::exit(1);
+    }
+
+    fs::copy(path, "/target/sparse-copy").unwrap();
+    let mut copy = vec![];
+    File::open("/target/sparse-copy").unwrap().read_to_end(&mut copy).unwrap();
+    if copy.len() as u64 != 4 * MB || &copy[..4] != b"head" ||
+       &copy[3 * MB as usize..3 * MB as usize + 4] != b"tail" ||
+       copy[MB as usize..3 * MB as usize].iter().any(|&b| b != 0) {
+        process::exit(1);
+    }
+}
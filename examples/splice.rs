@@ -0,0 +1,66 @@
+#![feature(iovec)]
+#![feature(splice)]
+
+use std::fs::File;
+use std::io::{self, IoSlice, Read, Write};
+use std::os::linux::io::{pipe, splice, tee, vmsplice, SPLICE_F_MOVE};
+use std::process;
+
+pub fn main() {
+    let mut contents = vec![];
+    for i in 0..10_000u32 {
+        contents.push((i % 251) as u8);
+    }
+    File::create("/target/splice-from").unwrap().write_all(&contents).unwrap();
+
+    // File to pipe through `io::copy`, which takes the `splice` path
+    let (mut rx, mut tx) = pipe().unwrap();
+    let mut from = File::open("/target/splice-from").unwrap();
+    let n = io::copy(&mut from, &mut tx).unwrap();
+    if n != contents.len() as u64 {
+        process::exit(1);
+    }
+
+    // Memory to pipe
+    let n = vmsplice(&tx, &[IoSlice::new(b"tail")], 0).unwrap();
+    if n != 4 {
+        process::exit(1);
+    }
+    drop(tx);
+
+    // Pipe to pipe, without consuming anything from `rx`
+    let (mut copy_rx, copy_tx) = pipe().unwrap();
+    let n = tee(&rx, &copy_tx, 1 << 16, 0).unwrap();
+    drop(copy_tx);
+    if n != contents.len() + 4 {
+        process::exit(1);
+    }
+
+    // Pipe to file, at an explicit offset
+    let to = File::create("/target/splice-to").unwrap();
+    let mut offset = 0;
+    loop {
+        match splice(&rx, None, &to, Some(&mut offset), 1 << 16, SPLICE_F_MOVE) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(_) => process::exit(1),
+        }
+    }
+    if offset != contents.len() as u64 + 4 {
+        process::exit(1);
+    }
+    let mut rest = vec![];
+    if rx.read_to_end(&mut rest).unwrap() != 0 {
+        process::exit(1);
+    }
+
+    contents.extend_from_slice(b"tail");
+    let mut spliced = vec![];
+    File::open("/target/splice-to").unwrap().read_to_end(&mut spliced).unwrap();
+    let mut teed = vec![];
+    copy_rx.read_to_end(&mut teed).unwrap();
+    println!("{} {}", spliced.len(), teed.len());
+    if spliced != contents || teed != contents {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,62 @@
+#![feature(is_terminal)]
+#![feature(termios)]
+
+use std::fs::File;
+use std::io;
+use std::os::linux::termios::{self, Termios, When};
+use std::os::unix::io::{is_terminal, IsTerminal};
+use std::process;
+
+pub fn main() {
+    let file = File::create("/target/termios").unwrap();
+    if file.is_terminal() || is_terminal(&file) {
+        process::exit(1);
+    }
+    // ENOTTY
+    match Termios::get(&file) {
+        Err(ref e) if e.raw_os_error() == Some(25) => {}
+        _ => process::exit(1),
+    }
+    match termios::window_size(&file) {
+        Err(ref e) if e.raw_os_error() == Some(25) => {}
+        _ => process::exit(1),
+    }
+    if termios::raw_mode(&file).is_ok() {
+        process::exit(1);
+    }
+
+    // Only when run interactively
+    let stdin = io::stdin();
+    if !stdin.is_terminal() {
+        return;
+    }
+    let saved = Termios::get(&stdin).unwrap();
+    println!("{:?} {:?}", saved, termios::window_size(&stdin).unwrap());
+    {
+        let guard = termios::raw_mode(&stdin).unwrap();
+        let raw = Termios::get(&stdin).unwrap();
+        if raw.echo() || raw.canonical() ||
+           guard.saved().echo() != saved.echo() {
+            process::exit(1);
+        }
+    }
+    {
+        let _guard = termios::echo_off(&stdin).unwrap();
+        if Termios::get(&stdin).unwrap().echo() {
+            process::exit(1);
+        }
+    }
+    let restored = Termios::get(&stdin).unwrap();
+    if restored.echo() != saved.echo() ||
+       restored.canonical() != saved.canonical() {
+        process::exit(1);
+    }
+
+    let mut t = saved;
+    t.set_canonical(false);
+    t.set(&stdin, When::Now).unwrap();
+    if Termios::get(&stdin).unwrap().canonical() {
+        process::exit(1);
+    }
+    saved.set(&stdin, When::Flush).unwrap();
+}
@@ -0,0 +1,82 @@
+#![feature(timerfd)]
+
+use std::io::ErrorKind;
+use std::os::linux::timerfd::{Clock, TimerFd};
+use std::process;
+use std::time::{Duration, Instant, SystemTime};
+
+pub fn main() {
+    let timer = TimerFd::new(Clock::Monotonic).unwrap();
+    if timer.remaining().unwrap().is_some() {
+        process::exit(1);
+    }
+
+    // One-shot, relative
+    let start = Instant::now();
+    timer.set(Duration::from_millis(20), None).unwrap();
+    if timer.interval().unwrap().is_some() {
+        process::exit(1);
+    }
+    let n = timer.wait().unwrap();
+    if n != 1 || start.elapsed() < Duration::from_millis(20) {
+        process::exit(1);
+    }
+    if timer.remaining().unwrap().is_some() {
+        process::exit(1);
+    }
+
+    // Periodic, from an absolute deadline
+    let start = Instant::now();
+    let period = Duration::from_millis(10);
+    timer.set_at(start + period, Some(period)).unwrap();
+    if timer.interval().unwrap() != Some(period) {
+        process::exit(1);
+    }
+    match timer.next_expiry().unwrap() {
+        Some(next) if next > start => {}
+        _ => process::exit(1),
+    }
+    let mut expirations = 0;
+    while expirations < 3 {
+        expirations += timer.wait().unwrap();
+    }
+    println!("{} {:?}", expirations, start.elapsed());
+    if start.elapsed() < period * 3 {
+        process::exit(1);
+    }
+
+    // Disarmed
+    timer.disarm().unwrap();
+    timer.set_nonblocking(true).unwrap();
+    match timer.wait() {
+        Err(ref e) if e.kind() == ErrorKind::WouldBlock => {}
+        _ => process::exit(1),
+    }
+
+    if timer.next_expiry().unwrap().is_some() {
+        process::exit(1);
+    }
+
+    // Other clocks
+    for &clock in &[Clock::Realtime, Clock::Boottime] {
+        let timer = TimerFd::new(clock).unwrap();
+        timer.set(Duration::new(0, 0), None).unwrap();
+        if timer.wait().unwrap() != 1 {
+            process::exit(1);
+        }
+        match timer.set_at(Instant::now(), None) {
+            Err(ref e) if e.kind() == ErrorKind::InvalidInput => {}
+            _ => process::exit(1),
+        }
+    }
+    let timer = TimerFd::new(Clock::Realtime).unwrap();
+    timer.set_at_system_time(SystemTime::now(), None).unwrap();
+    if timer.wait().unwrap() != 1 {
+        process::exit(1);
+    }
+    let timer = TimerFd::new(Clock::Monotonic).unwrap();
+    match timer.set_at_system_time(SystemTime::now(), None) {
+        Err(ref e) if e.kind() == ErrorKind::InvalidInput => {}
+        _ => process::exit(1),
+    }
+}
@@ -0,0 +1,28 @@
+#![feature(file_set_times)]
+#![feature(unix_chown)]
+
+use std::fs::{self, File, FileTimes};
+use std::os::unix::fs::{self as unix_fs, MetadataExt};
+use std::process;
+use std::time::{Duration, UNIX_EPOCH};
+
+pub fn main() {
+    let path = "/target/times";
+    let f = File::create(path).unwrap();
+
+    let accessed = UNIX_EPOCH + Duration::new(1_000_000_000, 123_456_789);
+    let modified = UNIX_EPOCH + Duration::new(1_234_567_890, 0);
+    f.set_times(FileTimes::new().set_accessed(accessed)).unwrap();
+    fs::set_times(path, FileTimes::new().set_modified(modified)).unwrap();
+
+    let m = fs::metadata(path).unwrap();
+    println!("{:?} {:?}", m.accessed().unwrap(), m.modified().unwrap());
+    if m.accessed().unwrap() != accessed || m.modified().unwrap() != modified {
+        process::exit(1);
+    }
+
+    // Changing the owner to ourselves is always allowed.
+    unix_fs::chown(path, Some(m.uid()), None).unwrap();
+    unix_fs::fchown(&f, None, Some(m.gid())).unwrap();
+    unix_fs::lchown(path, None, None).unwrap();
+}
@@ -0,0 +1,62 @@
+#![feature(file_offset)]
+#![feature(iovec)]
+
+use std::fs::OpenOptions;
+use std::io::{self, IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write};
+use std::os::unix::fs::FileExt;
+use std::process;
+
+pub fn main() {
+    let mut f = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open("/target/vectored")
+        .unwrap();
+
+    let n = f.write_vectored(&[IoSlice::new(b"Hello, "),
+                               IoSlice::new(b""),
+                               IoSlice::new(b"world!\n")])
+        .unwrap();
+    if n != 14 {
+        process::exit(1);
+    }
+
+    f.seek(SeekFrom::Start(0)).unwrap();
+    let mut hello = [0; 7];
+    let mut world = [0; 16];
+    let n = {
+        let mut bufs = [IoSliceMut::new(&mut hello),
+                        IoSliceMut::new(&mut world)];
+        f.read_vectored(&mut bufs).unwrap()
+    };
+    if n != 14 || &hello != b"Hello, " || &world[..7] != b"world!\n" {
+        process::exit(1);
+    }
+
+    // Past 4 GiB, so that both halves of the offset matter on 32-bit
+    let offset = (1 << 32) + 3;
+    let n = f.write_vectored_at(&[IoSlice::new(b"far "), IoSlice::new(b"away\n")],
+                                offset)
+        .unwrap();
+    if n != 9 {
+        process::exit(1);
+    }
+    let mut far = [0; 4];
+    let mut away = [0; 5];
+    let n = {
+        let mut bufs = [IoSliceMut::new(&mut far), IoSliceMut::new(&mut away)];
+        f.read_vectored_at(&mut bufs, offset).unwrap()
+    };
+    if n != 9 || &far != b"far " || &away != b"away\n" {
+        process::exit(1);
+    }
+    // The cursor is left where `read_vectored` put it
+    if f.seek(SeekFrom::Current(0)).unwrap() != 14 {
+        process::exit(1);
+    }
+
+    io::stdout().write_vectored(&[IoSlice::new(&hello), IoSlice::new(&world[..7])])
+        .unwrap();
+}
@@ -0,0 +1,67 @@
+#![feature(walk_dir)]
+
+use std::cmp::Ordering;
+use std::fs::{self, File};
+use std::os::unix::fs::symlink;
+use std::path::PathBuf;
+use std::process;
+
+fn walk(walk: fs::WalkDir) -> Vec<(usize, PathBuf)> {
+    walk.map(|e| {
+            let e = e.unwrap();
+            (e.depth(), e.path().strip_prefix("/target/walk-dir").unwrap().to_owned())
+        })
+        .collect()
+}
+
+fn by_name(a: &fs::WalkEntry, b: &fs::WalkEntry) -> Ordering {
+    a.file_name().cmp(b.file_name())
+}
+
+fn paths(expected: &[(usize, &str)]) -> Vec<(usize, PathBuf)> {
+    expected.iter().map(|&(depth, path)| (depth, PathBuf::from(path))).collect()
+}
+
+pub fn main() {
+    let root = "/target/walk-dir";
+    fs::remove_dir_all(root).ok();
+    fs::create_dir_all("/target/walk-dir/a/b").unwrap();
+    fs::create_dir_all("/target/walk-dir/c").unwrap();
+    File::create("/target/walk-dir/a/b/file").unwrap();
+    File::create("/target/walk-dir/c/file").unwrap();
+    // A link back to the root
+    symlink("..", "/target/walk-dir/c/loop").unwrap();
+
+    let depth_first = walk(fs::walk_dir(root).sort_by(by_name));
+    println!("{:?}", depth_first);
+    if depth_first != paths(&[(0, ""), (1, "a"), (2, "a/b"), (3, "a/b/file"), (1, "c"),
+                              (2, "c/file"), (2, "c/loop")]) {
+        process::exit(1);
+    }
+
+    let breadth_first = walk(fs::walk_dir(root)
+        .breadth_first(true)
+        .min_depth(1)
+        .max_depth(2)
+        .sort_by(by_name));
+    println!("{:?}", breadth_first);
+    if breadth_first != paths(&[(1, "a"), (1, "c"), (2, "a/b"), (2, "c/file"), (2, "c/loop")]) {
+        process::exit(1);
+    }
+
+    let filtered = walk(fs::walk_dir(root).filter_entry(|e| e.file_name() != "a"));
+    if filtered.iter().any(|&(_, ref path)| path.starts_with("a")) || filtered.len() != 4 {
+        process::exit(1);
+    }
+
+    // Following links finds the loop instead of walking forever
+    let mut loops = 0;
+    for entry in fs::walk_dir(root).follow_links(true) {
+        if entry.is_err() {
+            loops += 1;
+        }
+    }
+    if loops != 1 {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,36 @@
+#![feature(xattr)]
+
+use std::ffi::OsString;
+use std::fs::File;
+use std::os::linux::fs::xattr;
+use std::process;
+
+pub fn main() {
+    let path = "/target/xattr";
+    let f = File::create(path).unwrap();
+
+    if xattr::set(path, "user.steed", b"hello").is_err() {
+        // The filesystem doesn't do user xattrs, nothing else to test
+        return;
+    }
+    xattr::fset(&f, "user.empty", b"").unwrap();
+
+    let mut names = xattr::list(path).unwrap().collect::<Vec<_>>();
+    names.sort();
+    println!("{:?}", names);
+    if names != [OsString::from("user.empty"), OsString::from("user.steed")] {
+        process::exit(1);
+    }
+
+    if xattr::fget(&f, "user.steed").unwrap() != Some(b"hello".to_vec()) ||
+       xattr::get(path, "user.empty").unwrap() != Some(vec![]) ||
+       xattr::lget(path, "user.missing").unwrap() != None {
+        process::exit(1);
+    }
+
+    xattr::remove(path, "user.steed").unwrap();
+    xattr::fremove(&f, "user.empty").unwrap();
+    if xattr::flist(&f).unwrap().count() != 0 {
+        process::exit(1);
+    }
+}
@@ -42,6 +42,7 @@ pub type c_ulong = u64;
 pub type c_uchar = u8;
 pub type c_int = i32;
 pub type c_uint = u32;
+pub type c_short = i16;
 pub type c_ushort = u16;
 pub type c_longlong = i64;
 
@@ -17,13 +17,17 @@
 
 #![stable(feature = "rust1", since = "1.0.0")]
 
+use cmp;
+use collections::VecDeque;
 use fmt;
-use ffi::OsString;
-use io::{self, SeekFrom, Seek, Read, Write};
+use ffi::{OsStr, OsString};
+use io::{self, IoSlice, IoSliceMut, SeekFrom, Seek, Read, Write};
 use path::{Path, PathBuf};
+use rc::Rc;
 use sys::fs as fs_imp;
 use sys_common::{AsInnerMut, FromInner, AsInner, IntoInner};
 use time::SystemTime;
+use vec;
 
 /// A reference to an open file on the filesystem.
 ///
@@ -348,6 +352,176 @@ impl File {
             inner: self.inner.duplicate()?
         })
     }
+
+    /// Acquires a shared advisory lock on the file, blocking until it can be
+    /// taken.
+    ///
+    /// Any number of handles can hold a shared lock at the same time, but
+    /// none of them while some handle holds an exclusive lock. The lock
+    /// belongs to the underlying open file description, so it is shared with
+    /// handles created by `try_clone` and it's released once all of them are
+    /// closed, or by `unlock`.
+    ///
+    /// Locks are advisory: they don't prevent any I/O on the file, they only
+    /// exclude other lock holders.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function with
+    /// `LOCK_SH` on Unix.
+    /// Note that, this [may change in the future][changes].
+    /// [changes]: ../io/index.html#platform-specific-behavior
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// # fn foo() -> std::io::Result<()> {
+    /// let f = try!(File::open("state.db"));
+    /// try!(f.lock_shared());
+    /// // read the state
+    /// try!(f.unlock());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "0")]
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.inner.lock_shared()
+    }
+
+    /// Acquires an exclusive advisory lock on the file, blocking until it can
+    /// be taken.
+    ///
+    /// No other handle can hold any lock while an exclusive lock is held. See
+    /// `lock_shared` for the ownership of the lock.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `flock` function with
+    /// `LOCK_EX` on Unix.
+    /// Note that, this [may change in the future][changes].
+    /// [changes]: ../io/index.html#platform-specific-behavior
+    #[unstable(feature = "file_lock", issue = "0")]
+    pub fn lock_exclusive(&self) -> io::Result<()> {
+        self.inner.lock_exclusive()
+    }
+
+    /// Tries to acquire a shared advisory lock on the file, without blocking.
+    ///
+    /// Returns `Ok(false)` if another handle holds an exclusive lock.
+    #[unstable(feature = "file_lock", issue = "0")]
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.inner.try_lock_shared()
+    }
+
+    /// Tries to acquire an exclusive advisory lock on the file, without
+    /// blocking.
+    ///
+    /// Returns `Ok(false)` if another handle holds any lock.
+    #[unstable(feature = "file_lock", issue = "0")]
+    pub fn try_lock_exclusive(&self) -> io::Result<bool> {
+        self.inner.try_lock_exclusive()
+    }
+
+    /// Releases the advisory lock held on the file, if any.
+    #[unstable(feature = "file_lock", issue = "0")]
+    pub fn unlock(&self) -> io::Result<()> {
+        self.inner.unlock()
+    }
+
+    /// Changes the timestamps of the underlying file.
+    ///
+    /// Timestamps that haven't been set in `times` are left unchanged.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to the `futimens` function on Unix.
+    /// Note that, this [may change in the future][changes].
+    /// [changes]: ../io/index.html#platform-specific-behavior
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_set_times)]
+    /// use std::fs::{File, FileTimes};
+    /// use std::time::SystemTime;
+    ///
+    /// # fn foo() -> std::io::Result<()> {
+    /// let f = try!(File::open("foo.txt"));
+    /// let times = FileTimes::new()
+    ///     .set_accessed(SystemTime::now())
+    ///     .set_modified(SystemTime::now());
+    /// try!(f.set_times(times));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[unstable(feature = "file_set_times", issue = "0")]
+    pub fn set_times(&self, times: FileTimes) -> io::Result<()> {
+        self.inner.set_times(times.0)
+    }
+
+    /// Changes the modification time of the underlying file.
+    ///
+    /// This is an alias for `set_times(FileTimes::new().set_modified(time))`.
+    #[unstable(feature = "file_set_times", issue = "0")]
+    pub fn set_modified(&self, time: SystemTime) -> io::Result<()> {
+        self.set_times(FileTimes::new().set_modified(time))
+    }
+}
+
+/// Representation of the timestamps of a file, to be changed with
+/// `File::set_times`, `fs::set_times` or `fs::set_symlink_times`.
+///
+/// Timestamps that aren't set are left unchanged on the file.
+#[derive(Copy, Clone, Debug)]
+#[unstable(feature = "file_set_times", issue = "0")]
+pub struct FileTimes(fs_imp::FileTimes);
+
+impl FileTimes {
+    /// Creates a new `FileTimes` with no times set.
+    #[unstable(feature = "file_set_times", issue = "0")]
+    pub fn new() -> FileTimes {
+        FileTimes(fs_imp::FileTimes::new())
+    }
+
+    /// Sets the last access time of a file.
+    #[unstable(feature = "file_set_times", issue = "0")]
+    pub fn set_accessed(mut self, t: SystemTime) -> FileTimes {
+        self.0.set_accessed(*t.as_inner());
+        self
+    }
+
+    /// Sets the last modified time of a file.
+    #[unstable(feature = "file_set_times", issue = "0")]
+    pub fn set_modified(mut self, t: SystemTime) -> FileTimes {
+        self.0.set_modified(*t.as_inner());
+        self
+    }
+
+    /// Sets the last access time of a file to the current time, as read by
+    /// the kernel when the timestamps are applied.
+    #[unstable(feature = "file_set_times", issue = "0")]
+    pub fn set_accessed_now(mut self) -> FileTimes {
+        self.0.set_accessed_now();
+        self
+    }
+
+    /// Sets the last modified time of a file to the current time, as read by
+    /// the kernel when the timestamps are applied.
+    #[unstable(feature = "file_set_times", issue = "0")]
+    pub fn set_modified_now(mut self) -> FileTimes {
+        self.0.set_modified_now();
+        self
+    }
+}
+
+#[unstable(feature = "file_set_times", issue = "0")]
+impl Default for FileTimes {
+    fn default() -> FileTimes {
+        FileTimes::new()
+    }
 }
 
 impl AsInner<fs_imp::File> for File {
@@ -376,7 +550,11 @@ impl Read for File {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         self.inner.read(buf)
     }
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        self.inner.read_vectored(bufs)
+    }
     fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        buf.reserve(initial_buffer_size(self));
         self.inner.read_to_end(buf)
     }
 }
@@ -385,6 +563,9 @@ impl Write for File {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         self.inner.write(buf)
     }
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        self.inner.write_vectored(bufs)
+    }
     fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -398,7 +579,11 @@ impl<'a> Read for &'a File {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         self.inner.read(buf)
     }
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        self.inner.read_vectored(bufs)
+    }
     fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        buf.reserve(initial_buffer_size(self));
         self.inner.read_to_end(buf)
     }
 }
@@ -407,6 +592,9 @@ impl<'a> Write for &'a File {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         self.inner.write(buf)
     }
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        self.inner.write_vectored(bufs)
+    }
     fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -416,6 +604,94 @@ impl<'a> Seek for &'a File {
     }
 }
 
+/// A file that atomically replaces another file once it's been written.
+///
+/// The new contents are written to a temporary file in the same directory as
+/// the file being replaced. `commit` then syncs the temporary file to disk
+/// and renames it over the target, so readers see either the old or the new
+/// contents, never a mix of both, even if the system crashes halfway
+/// through.
+///
+/// The temporary file is created with `O_TMPFILE` where the kernel and the
+/// filesystem support it, which keeps it invisible until it's committed.
+/// Elsewhere it gets a unique hidden name. Either way an `AtomicFile`
+/// dropped without being committed leaves no trace.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(atomic_file)]
+/// use std::fs::AtomicFile;
+/// use std::io::prelude::*;
+///
+/// # fn foo() -> std::io::Result<()> {
+/// let mut f = try!(AtomicFile::new("settings.toml"));
+/// try!(f.write_all(b"verbose = true\n"));
+/// try!(f.commit());
+/// # Ok(())
+/// # }
+/// ```
+#[unstable(feature = "atomic_file", issue = "0")]
+#[derive(Debug)]
+pub struct AtomicFile {
+    file: File,
+    inner: fs_imp::AtomicFile,
+}
+
+impl AtomicFile {
+    /// Creates an empty temporary file that will replace the file at `path`
+    /// once committed. The file is open for reading and writing.
+    ///
+    /// The file at `path` doesn't need to exist, but its parent directory
+    /// does.
+    #[unstable(feature = "atomic_file", issue = "0")]
+    pub fn new<P: AsRef<Path>>(path: P) -> io::Result<AtomicFile> {
+        let (file, inner) = fs_imp::AtomicFile::new(path.as_ref())?;
+        Ok(AtomicFile { file: File { inner: file }, inner: inner })
+    }
+
+    /// Returns a reference to the temporary file, e.g. to set its
+    /// permissions before it's committed.
+    #[unstable(feature = "atomic_file", issue = "0")]
+    pub fn as_file(&self) -> &File {
+        &self.file
+    }
+
+    /// Returns a mutable reference to the temporary file.
+    #[unstable(feature = "atomic_file", issue = "0")]
+    pub fn as_file_mut(&mut self) -> &mut File {
+        &mut self.file
+    }
+
+    /// Syncs the file to disk and atomically moves it into place, replacing
+    /// the target file if it exists. The directory is synced as well so the
+    /// replacement survives a crash.
+    #[unstable(feature = "atomic_file", issue = "0")]
+    pub fn commit(mut self) -> io::Result<()> {
+        self.file.sync_all()?;
+        self.inner.commit(&self.file.inner, true)
+    }
+
+    /// Like `commit`, but fails with `ErrorKind::AlreadyExists` instead of
+    /// replacing an existing target file.
+    #[unstable(feature = "atomic_file", issue = "0")]
+    pub fn commit_new(mut self) -> io::Result<()> {
+        self.file.sync_all()?;
+        self.inner.commit(&self.file.inner, false)
+    }
+}
+
+#[unstable(feature = "atomic_file", issue = "0")]
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.write(buf)
+    }
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        self.file.write_vectored(bufs)
+    }
+    fn flush(&mut self) -> io::Result<()> { self.file.flush() }
+}
+
 impl OpenOptions {
     /// Creates a blank new set of options ready for configuration.
     ///
@@ -607,6 +883,10 @@ impl OpenOptions {
     }
 }
 
+impl AsInner<fs_imp::OpenOptions> for OpenOptions {
+    fn as_inner(&self) -> &fs_imp::OpenOptions { &self.0 }
+}
+
 impl AsInnerMut<fs_imp::OpenOptions> for OpenOptions {
     fn as_inner_mut(&mut self) -> &mut fs_imp::OpenOptions { &mut self.0 }
 }
@@ -802,6 +1082,9 @@ impl Metadata {
 impl AsInner<fs_imp::FileAttr> for Metadata {
     fn as_inner(&self) -> &fs_imp::FileAttr { &self.0 }
 }
+impl FromInner<fs_imp::FileAttr> for Metadata {
+    fn from_inner(attr: fs_imp::FileAttr) -> Metadata { Metadata(attr) }
+}
 
 impl Permissions {
     /// Returns whether these permissions describe a readonly file.
@@ -923,6 +1206,10 @@ impl AsInner<fs_imp::FilePermissions> for Permissions {
     fn as_inner(&self) -> &fs_imp::FilePermissions { &self.0 }
 }
 
+impl FromInner<fs_imp::ReadDir> for ReadDir {
+    fn from_inner(inner: fs_imp::ReadDir) -> ReadDir { ReadDir(inner) }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl Iterator for ReadDir {
     type Item = io::Result<DirEntry>;
@@ -1069,6 +1356,112 @@ impl AsInner<fs_imp::DirEntry> for DirEntry {
     fn as_inner(&self) -> &fs_imp::DirEntry { &self.0 }
 }
 
+/// Reads the entire contents of a file into a bytes vector.
+///
+/// This is a convenience function for using `File::open` and `read_to_end`
+/// with fewer imports and without an intermediate variable. The buffer is
+/// allocated up front from the size of the file, and grown as needed for
+/// files that report a size of zero, like those in procfs and sysfs.
+///
+/// # Errors
+///
+/// This function will return an error if `path` does not already exist.
+/// Other errors may also be returned according to `OpenOptions::open`, and
+/// any error encountered by `read` other than `ErrorKind::Interrupted` is
+/// returned as well.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_read_write)]
+/// use std::fs;
+///
+/// # fn foo() -> std::io::Result<()> {
+/// let bytes = try!(fs::read("address.txt"));
+/// # Ok(())
+/// # }
+/// ```
+#[unstable(feature = "fs_read_write", issue = "0")]
+pub fn read<P: AsRef<Path>>(path: P) -> io::Result<Vec<u8>> {
+    let file = File::open(path)?;
+    let mut bytes = Vec::with_capacity(initial_buffer_size(&file));
+    // Not `File::read_to_end`, which would size the buffer again
+    file.inner.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Reads the entire contents of a file into a string.
+///
+/// This is a convenience function for using `File::open` and
+/// `read_to_string` with fewer imports and without an intermediate variable.
+/// The buffer is sized like the one of `fs::read`.
+///
+/// # Errors
+///
+/// This function will return an error if `path` does not already exist.
+/// Other errors may also be returned according to `OpenOptions::open`.
+///
+/// If the contents of the file are not valid UTF-8, then an error will also
+/// be returned.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_read_write)]
+/// use std::fs;
+///
+/// # fn foo() -> std::io::Result<()> {
+/// let address = try!(fs::read_to_string("address.txt"));
+/// # Ok(())
+/// # }
+/// ```
+#[unstable(feature = "fs_read_write", issue = "0")]
+pub fn read_to_string<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    String::from_utf8(read(path)?).map_err(|_| {
+        io::Error::new(io::ErrorKind::InvalidData,
+                       "stream did not contain valid UTF-8")
+    })
+}
+
+/// Writes a slice as the entire contents of a file.
+///
+/// This function will create a file if it does not exist, and will entirely
+/// replace its contents if it does.
+///
+/// This is a convenience function for using `File::create` and `write_all`
+/// with fewer imports.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_read_write)]
+/// use std::fs;
+///
+/// # fn foo() -> std::io::Result<()> {
+/// try!(fs::write("bar.txt", "boom"));
+/// # Ok(())
+/// # }
+/// ```
+#[unstable(feature = "fs_read_write", issue = "0")]
+pub fn write<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C)
+                                             -> io::Result<()> {
+    File::create(path)?.write_all(contents.as_ref())
+}
+
+// The capacity to allocate for reading the rest of `file` in one go: the
+// bytes between the cursor and the end of the file, plus one so that the
+// final `read`, the one returning 0, doesn't make the buffer grow. Files in
+// procfs and sysfs report a size of zero, so the buffer grows as usual for
+// them.
+fn initial_buffer_size(file: &File) -> usize {
+    let size = match file.metadata() {
+        Ok(metadata) => metadata.len(),
+        Err(_) => return 0,
+    };
+    let pos = file.inner.seek(SeekFrom::Current(0)).unwrap_or(0);
+    size.saturating_sub(pos) as usize + 1
+}
+
 /// Removes a file from the filesystem.
 ///
 /// Note that there is no
@@ -1232,6 +1625,8 @@ pub fn rename<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<()>
 /// This function currently corresponds to the `open` function in Unix
 /// with `O_RDONLY` for `from` and `O_WRONLY`, `O_CREAT`, and `O_TRUNC` for `to`.
 /// `O_CLOEXEC` is set for returned file descriptors.
+/// On Linux, the contents are shared with `ioctl(FICLONE)` or copied inside of
+/// the kernel with `copy_file_range` or `sendfile` whenever possible.
 /// On Windows, this function currently corresponds to `CopyFileEx`.
 /// Note that, this [may change in the future][changes].
 /// [changes]: ../io/index.html#platform-specific-behavior
@@ -1605,6 +2000,515 @@ pub fn set_permissions<P: AsRef<Path>>(path: P, perm: Permissions)
     fs_imp::set_perm(path.as_ref(), perm.0)
 }
 
+/// Changes the timestamps of a file or a directory.
+///
+/// This function will traverse symbolic links to change the timestamps of the
+/// file they point to, see `set_symlink_times` for the symlink itself.
+///
+/// # Platform-specific behavior
+///
+/// This function currently corresponds to the `utimensat` function on Unix.
+/// Note that, this [may change in the future][changes].
+/// [changes]: ../io/index.html#platform-specific-behavior
+///
+/// # Errors
+///
+/// This function will return an error in the following situations, but is not
+/// limited to just these cases:
+///
+/// * `path` does not exist.
+/// * The user isn't the owner of the file and lacks the permission to change
+///   its timestamps.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(file_set_times)]
+/// use std::fs::{self, FileTimes};
+/// use std::time::UNIX_EPOCH;
+///
+/// # fn foo() -> std::io::Result<()> {
+/// try!(fs::set_times("foo.txt", FileTimes::new().set_modified(UNIX_EPOCH)));
+/// # Ok(())
+/// # }
+/// ```
+#[unstable(feature = "file_set_times", issue = "0")]
+pub fn set_times<P: AsRef<Path>>(path: P, times: FileTimes) -> io::Result<()> {
+    fs_imp::set_times(path.as_ref(), times.0, true)
+}
+
+/// Changes the timestamps of a file, a directory or a symbolic link, without
+/// following symbolic links.
+///
+/// # Platform-specific behavior
+///
+/// This function currently corresponds to the `utimensat` function with the
+/// `AT_SYMLINK_NOFOLLOW` flag on Unix.
+/// Note that, this [may change in the future][changes].
+/// [changes]: ../io/index.html#platform-specific-behavior
+#[unstable(feature = "file_set_times", issue = "0")]
+pub fn set_symlink_times<P: AsRef<Path>>(path: P, times: FileTimes)
+                                         -> io::Result<()> {
+    fs_imp::set_times(path.as_ref(), times.0, false)
+}
+
+/// Returns an iterator that recursively walks the directory tree rooted at
+/// `path`.
+///
+/// The iterator yields the root itself first, at depth 0, then every entry
+/// below it. The order of the walk, whether symbolic links are followed,
+/// how deep it goes and which entries it yields can be changed with the
+/// methods of `WalkDir` before iterating.
+///
+/// Directories are opened relative to their parent with `openat`, so the
+/// walk doesn't resolve ever longer paths, and the type of each entry is
+/// taken from the directory listing when the filesystem provides it, so
+/// entries aren't `stat`ed one by one.
+///
+/// # Errors
+///
+/// Errors reading an entry are yielded in place of the entry. Errors
+/// opening a directory, or detecting a symbolic link loop, are yielded
+/// right after the entry of the directory. Either way the walk carries on
+/// with the next entry.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(walk_dir)]
+/// use std::fs;
+///
+/// # fn foo() -> std::io::Result<()> {
+/// let walk = fs::walk_dir("/usr/share/doc")
+///     .max_depth(2)
+///     .filter_entry(|e| e.file_name() != "examples")
+///     .sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+/// for entry in walk {
+///     let entry = try!(entry);
+///     println!("{} {}", entry.depth(), entry.path().display());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[unstable(feature = "walk_dir", issue = "0")]
+pub fn walk_dir<P: AsRef<Path>>(path: P) -> WalkDir {
+    WalkDir {
+        root: Some(path.as_ref().to_path_buf()),
+        breadth_first: false,
+        follow_links: false,
+        min_depth: 0,
+        max_depth: usize::max_value(),
+        filter: None,
+        sort: None,
+        stack: Vec::new(),
+        queue: VecDeque::new(),
+        deferred: None,
+    }
+}
+
+/// A recursive directory iterator, created by `walk_dir`.
+///
+/// The options must be set before the first call to `next`.
+#[unstable(feature = "walk_dir", issue = "0")]
+pub struct WalkDir {
+    // Taken when the walk starts
+    root: Option<PathBuf>,
+    breadth_first: bool,
+    follow_links: bool,
+    min_depth: usize,
+    max_depth: usize,
+    filter: Option<Box<FnMut(&WalkEntry) -> bool>>,
+    sort: Option<Box<FnMut(&WalkEntry, &WalkEntry) -> cmp::Ordering>>,
+    // Directories being read. Depth-first walks descend into a directory
+    // as soon as they find it, so this is a stack with the directory being
+    // read on top. Breadth-first walks only ever read one directory at a
+    // time.
+    stack: Vec<WalkFrame>,
+    // Directories found by a breadth-first walk that are yet to be read
+    queue: VecDeque<WalkPending>,
+    // The error opening the directory yielded last, for the next call
+    deferred: Option<io::Error>,
+}
+
+/// An entry found by `WalkDir`.
+#[unstable(feature = "walk_dir", issue = "0")]
+#[derive(Clone)]
+pub struct WalkEntry {
+    path: PathBuf,
+    depth: usize,
+    file_type: FileType,
+    followed_link: bool,
+}
+
+struct WalkFrame {
+    dir: Rc<fs_imp::Dir>,
+    entries: WalkEntries,
+    depth: usize,
+    ancestors: Option<Rc<WalkAncestor>>,
+}
+
+enum WalkEntries {
+    Read(fs_imp::ReadDir),
+    Sorted(vec::IntoIter<io::Result<WalkEntry>>),
+}
+
+// A subdirectory of `parent` that a breadth-first walk will read later
+struct WalkPending {
+    parent: Rc<fs_imp::Dir>,
+    entry: WalkEntry,
+    ancestors: Option<Rc<WalkAncestor>>,
+}
+
+// The chain of directories a directory was reached through, to detect
+// symbolic link loops. Only kept when following links.
+struct WalkAncestor {
+    dev: u64,
+    ino: u64,
+    parent: Option<Rc<WalkAncestor>>,
+}
+
+impl WalkDir {
+    /// Walks the tree in breadth-first order, yielding all the entries at
+    /// one depth before those at the next one, instead of the default
+    /// depth-first order, where the contents of a directory are yielded
+    /// right after the directory.
+    ///
+    /// A breadth-first walk keeps a file descriptor open for each directory
+    /// with subdirectories that haven't been read yet, while a depth-first
+    /// walk keeps two per level of the directory being read.
+    #[unstable(feature = "walk_dir", issue = "0")]
+    pub fn breadth_first(mut self, breadth_first: bool) -> WalkDir {
+        self.breadth_first = breadth_first;
+        self
+    }
+
+    /// Follows symbolic links to directories, walking them like regular
+    /// directories. Defaults to `false`, in which case links are yielded
+    /// but not followed. The root is followed in either case.
+    ///
+    /// A link pointing back to one of its ancestors is reported as an
+    /// error instead of being walked forever.
+    #[unstable(feature = "walk_dir", issue = "0")]
+    pub fn follow_links(mut self, follow_links: bool) -> WalkDir {
+        self.follow_links = follow_links;
+        self
+    }
+
+    /// Doesn't yield entries shallower than `depth`. The walk still goes
+    /// through them. Setting this to 1 skips the root.
+    #[unstable(feature = "walk_dir", issue = "0")]
+    pub fn min_depth(mut self, depth: usize) -> WalkDir {
+        self.min_depth = depth;
+        self
+    }
+
+    /// Doesn't descend deeper than `depth`. Setting this to 1 only yields
+    /// the root and its direct entries.
+    #[unstable(feature = "walk_dir", issue = "0")]
+    pub fn max_depth(mut self, depth: usize) -> WalkDir {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Only yields the entries for which `filter` returns `true`. The walk
+    /// doesn't descend into directories that are filtered out.
+    #[unstable(feature = "walk_dir", issue = "0")]
+    pub fn filter_entry<F>(mut self, filter: F) -> WalkDir
+        where F: FnMut(&WalkEntry) -> bool + 'static
+    {
+        self.filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Yields the entries of each directory in the order given by `cmp`
+    /// instead of the order the filesystem lists them in.
+    ///
+    /// This has to read a whole directory before yielding any of its
+    /// entries.
+    #[unstable(feature = "walk_dir", issue = "0")]
+    pub fn sort_by<F>(mut self, cmp: F) -> WalkDir
+        where F: FnMut(&WalkEntry, &WalkEntry) -> cmp::Ordering + 'static
+    {
+        self.sort = Some(Box::new(cmp));
+        self
+    }
+
+    fn start(&mut self, root: PathBuf) -> Option<io::Result<WalkEntry>> {
+        let file_type = match fs_imp::stat(&root) {
+            Ok(attr) => FileType(attr.file_type()),
+            Err(e) => return Some(Err(e)),
+        };
+        let entry = WalkEntry {
+            path: root,
+            depth: 0,
+            file_type: file_type,
+            followed_link: false,
+        };
+        if !self.keep(&entry) {
+            return None;
+        }
+        if file_type.is_dir() && self.max_depth > 0 {
+            let frame = fs_imp::Dir::open(&entry.path).and_then(|dir| {
+                self.frame(dir, 0, None)
+            });
+            match frame {
+                Ok(frame) => self.stack.push(frame),
+                Err(e) => self.deferred = Some(e),
+            }
+        }
+        if self.min_depth > 0 {
+            return None;
+        }
+        Some(Ok(entry))
+    }
+
+    // Runs the filter, if any
+    fn keep(&mut self, entry: &WalkEntry) -> bool {
+        match self.filter {
+            Some(ref mut filter) => filter(entry),
+            None => true,
+        }
+    }
+
+    fn frame(&mut self,
+             dir: fs_imp::Dir,
+             depth: usize,
+             ancestors: Option<Rc<WalkAncestor>>)
+             -> io::Result<WalkFrame> {
+        let ancestors = if self.follow_links {
+            let attr = dir.file_attr()?;
+            let (dev, ino) = (attr.as_inner().st_dev as u64,
+                              attr.as_inner().st_ino as u64);
+            {
+                let mut ancestor = ancestors.as_ref();
+                while let Some(a) = ancestor {
+                    if a.dev == dev && a.ino == ino {
+                        return Err(io::Error::new(io::ErrorKind::Other,
+                                                  "symbolic link loop found"));
+                    }
+                    ancestor = a.parent.as_ref();
+                }
+            }
+            Some(Rc::new(WalkAncestor { dev: dev, ino: ino, parent: ancestors }))
+        } else {
+            None
+        };
+        let mut entries = dir.read_dir()?;
+        let dir = Rc::new(dir);
+        let follow_links = self.follow_links;
+        let entries = match self.sort {
+            Some(ref mut sort) => {
+                let mut sorted = vec![];
+                while let Some(entry) = entries.next() {
+                    sorted.push(entry.and_then(|e| {
+                        WalkEntry::new(&dir, e, depth + 1, follow_links)
+                    }));
+                }
+                // Errors go first
+                sorted.sort_by(|a, b| match (a, b) {
+                    (&Ok(ref a), &Ok(ref b)) => sort(a, b),
+                    (&Ok(_), &Err(_)) => cmp::Ordering::Greater,
+                    (&Err(_), &Ok(_)) => cmp::Ordering::Less,
+                    (&Err(_), &Err(_)) => cmp::Ordering::Equal,
+                });
+                WalkEntries::Sorted(sorted.into_iter())
+            }
+            None => WalkEntries::Read(entries),
+        };
+        Ok(WalkFrame {
+            dir: dir,
+            entries: entries,
+            depth: depth,
+            ancestors: ancestors,
+        })
+    }
+
+    // Opens the directory `entry` of `parent`
+    fn descend(&mut self,
+               parent: &fs_imp::Dir,
+               entry: &WalkEntry,
+               ancestors: Option<Rc<WalkAncestor>>)
+               -> io::Result<WalkFrame> {
+        let name = Path::new(entry.path.file_name().unwrap());
+        let dir = parent.open_subdir(name, entry.followed_link)?;
+        self.frame(dir, entry.depth, ancestors)
+    }
+}
+
+#[unstable(feature = "walk_dir", issue = "0")]
+impl Iterator for WalkDir {
+    type Item = io::Result<WalkEntry>;
+
+    fn next(&mut self) -> Option<io::Result<WalkEntry>> {
+        if let Some(root) = self.root.take() {
+            if let Some(entry) = self.start(root) {
+                return Some(entry);
+            }
+        }
+        let follow_links = self.follow_links;
+        loop {
+            if let Some(e) = self.deferred.take() {
+                return Some(Err(e));
+            }
+            let next = match self.stack.last_mut() {
+                Some(&mut WalkFrame { ref dir,
+                                      ref mut entries,
+                                      depth,
+                                      ref ancestors }) => {
+                    let next = match *entries {
+                        WalkEntries::Read(ref mut entries) => {
+                            entries.next().map(|entry| entry.and_then(|e| {
+                                WalkEntry::new(dir, e, depth + 1, follow_links)
+                            }))
+                        }
+                        WalkEntries::Sorted(ref mut entries) => entries.next(),
+                    };
+                    next.map(|e| (e, dir.clone(), ancestors.clone()))
+                }
+                None => None,
+            };
+            let (entry, dir, ancestors) = match next {
+                Some((Ok(entry), dir, ancestors)) => (entry, dir, ancestors),
+                Some((Err(e), _, _)) => return Some(Err(e)),
+                None => {
+                    // The directory on top of the stack, if any, is done
+                    self.stack.pop();
+                    if self.breadth_first {
+                        let pending = match self.queue.pop_front() {
+                            Some(pending) => pending,
+                            None => return None,
+                        };
+                        match self.descend(&pending.parent,
+                                           &pending.entry,
+                                           pending.ancestors) {
+                            Ok(frame) => self.stack.push(frame),
+                            Err(e) => return Some(Err(e)),
+                        }
+                    } else if self.stack.is_empty() {
+                        return None;
+                    }
+                    continue;
+                }
+            };
+
+            if !self.keep(&entry) {
+                continue;
+            }
+            if entry.file_type.is_dir() && entry.depth < self.max_depth {
+                if self.breadth_first {
+                    self.queue.push_back(WalkPending {
+                        parent: dir,
+                        entry: entry.clone(),
+                        ancestors: ancestors,
+                    });
+                } else {
+                    match self.descend(&dir, &entry, ancestors) {
+                        Ok(frame) => self.stack.push(frame),
+                        Err(e) => self.deferred = Some(e),
+                    }
+                }
+            }
+            if entry.depth >= self.min_depth {
+                return Some(Ok(entry));
+            }
+        }
+    }
+}
+
+#[unstable(feature = "walk_dir", issue = "0")]
+impl fmt::Debug for WalkDir {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("WalkDir")
+            .field("root", &self.root)
+            .field("breadth_first", &self.breadth_first)
+            .field("follow_links", &self.follow_links)
+            .field("min_depth", &self.min_depth)
+            .field("max_depth", &self.max_depth)
+            .finish()
+    }
+}
+
+impl WalkEntry {
+    fn new(dir: &fs_imp::Dir,
+           entry: fs_imp::DirEntry,
+           depth: usize,
+           follow_links: bool)
+           -> io::Result<WalkEntry> {
+        let mut file_type = entry.file_type_at(dir)?;
+        let followed_link = follow_links && file_type.is_symlink();
+        if followed_link {
+            file_type = dir.stat_at(Path::new(&entry.file_name()))?.file_type();
+        }
+        Ok(WalkEntry {
+            path: entry.path(),
+            depth: depth,
+            file_type: FileType(file_type),
+            followed_link: followed_link,
+        })
+    }
+
+    /// Returns the path of the entry: the root of the walk joined with the
+    /// names of the directories leading to the entry.
+    #[unstable(feature = "walk_dir", issue = "0")]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the path of the entry, consuming it.
+    #[unstable(feature = "walk_dir", issue = "0")]
+    pub fn into_path(self) -> PathBuf {
+        self.path
+    }
+
+    /// Returns the name of the entry, i.e. the last component of its path.
+    #[unstable(feature = "walk_dir", issue = "0")]
+    pub fn file_name(&self) -> &OsStr {
+        self.path.file_name().unwrap_or(self.path.as_os_str())
+    }
+
+    /// Returns the depth of the entry below the root, which is at depth 0.
+    #[unstable(feature = "walk_dir", issue = "0")]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the type of the entry. When following symbolic links, this
+    /// is the type of the target of a link.
+    #[unstable(feature = "walk_dir", issue = "0")]
+    pub fn file_type(&self) -> FileType {
+        self.file_type
+    }
+
+    /// Returns whether the entry is a symbolic link, whether it was
+    /// followed or not.
+    #[unstable(feature = "walk_dir", issue = "0")]
+    pub fn path_is_symlink(&self) -> bool {
+        self.followed_link || self.file_type.is_symlink()
+    }
+
+    /// Queries the metadata of the entry, of the target of a symbolic link
+    /// that was followed.
+    #[unstable(feature = "walk_dir", issue = "0")]
+    pub fn metadata(&self) -> io::Result<Metadata> {
+        if self.followed_link || self.depth == 0 {
+            metadata(&self.path)
+        } else {
+            symlink_metadata(&self.path)
+        }
+    }
+}
+
+#[unstable(feature = "walk_dir", issue = "0")]
+impl fmt::Debug for WalkEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("WalkEntry")
+            .field("path", &self.path)
+            .field("depth", &self.depth)
+            .field("file_type", &self.file_type)
+            .finish()
+    }
+}
+
 impl DirBuilder {
     /// Creates a new set of options with default mode/security settings for all
     /// platforms and also non-recursive.
@@ -3,7 +3,10 @@
 use core::fmt;
 
 use ctypes::c_int;
-use {cmp, linux, io, memchr, str};
+use marker::PhantomData;
+use ops::{Deref, DerefMut};
+use sys::fd::iovcnt;
+use {cmp, linux, io, memchr, slice, str};
 
 // Rust 1.14.0
 pub mod prelude;
@@ -52,6 +55,21 @@ pub trait Read {
             Ok(())
         }
     }
+    /// Like `read`, except that it reads into a slice of buffers.
+    ///
+    /// Data is copied to fill each buffer in order, with the final buffer
+    /// written to possibly being only partially filled. This method must
+    /// behave as a call to `read` with the buffers concatenated would.
+    ///
+    /// The default implementation calls `read` with the first nonempty
+    /// buffer provided, or an empty one if none exists.
+    #[unstable(feature = "iovec", issue = "0")]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> Result<usize> {
+        match bufs.iter_mut().find(|b| !b.is_empty()) {
+            Some(buf) => self.read(buf),
+            None => self.read(&mut []),
+        }
+    }
     #[stable(feature = "steed", since = "1.0.0")]
     fn by_ref(&mut self) -> &mut Self where Self: Sized { self }
 }
@@ -141,6 +159,21 @@ pub trait Write {
         }
         Ok(())
     }
+    /// Like `write`, except that it writes from a slice of buffers.
+    ///
+    /// Data is copied from each buffer in order, with the final buffer read
+    /// from possibly being only partially consumed. This method must behave
+    /// as a call to `write` with the buffers concatenated would.
+    ///
+    /// The default implementation calls `write` with the first nonempty
+    /// buffer provided, or an empty one if none exists.
+    #[unstable(feature = "iovec", issue = "0")]
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> Result<usize> {
+        match bufs.iter().find(|b| !b.is_empty()) {
+            Some(buf) => self.write(buf),
+            None => self.write(&[]),
+        }
+    }
     #[stable(feature = "steed", since = "1.0.0")]
     fn write_fmt(&mut self, fmt: fmt::Arguments) -> Result<()> {
         // Create a shim which translates a Write to a fmt::Write and saves
@@ -179,6 +212,122 @@ pub trait Write {
     fn by_ref(&mut self) -> &mut Self where Self: Sized { self }
 }
 
+/// A buffer type used with `Read::read_vectored`.
+///
+/// It has the same layout as `struct iovec`, so a slice of `IoSliceMut`s can
+/// be handed to `readv` as is.
+#[unstable(feature = "iovec", issue = "0")]
+#[repr(C)]
+pub struct IoSliceMut<'a> {
+    vec: linux::iovec,
+    _p: PhantomData<&'a mut [u8]>,
+}
+
+#[unstable(feature = "iovec", issue = "0")]
+unsafe impl<'a> Send for IoSliceMut<'a> {}
+
+#[unstable(feature = "iovec", issue = "0")]
+unsafe impl<'a> Sync for IoSliceMut<'a> {}
+
+impl<'a> IoSliceMut<'a> {
+    /// Creates a new `IoSliceMut` wrapping a byte slice.
+    #[unstable(feature = "iovec", issue = "0")]
+    #[inline]
+    pub fn new(buf: &'a mut [u8]) -> IoSliceMut<'a> {
+        IoSliceMut {
+            vec: linux::iovec {
+                iov_base: buf.as_mut_ptr() as *mut _,
+                iov_len: buf.len(),
+            },
+            _p: PhantomData,
+        }
+    }
+}
+
+#[unstable(feature = "iovec", issue = "0")]
+impl<'a> Deref for IoSliceMut<'a> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        unsafe {
+            slice::from_raw_parts(self.vec.iov_base as *const u8,
+                                  self.vec.iov_len)
+        }
+    }
+}
+
+#[unstable(feature = "iovec", issue = "0")]
+impl<'a> DerefMut for IoSliceMut<'a> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        unsafe {
+            slice::from_raw_parts_mut(self.vec.iov_base as *mut u8,
+                                      self.vec.iov_len)
+        }
+    }
+}
+
+#[unstable(feature = "iovec", issue = "0")]
+impl<'a> fmt::Debug for IoSliceMut<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+/// A buffer type used with `Write::write_vectored`.
+///
+/// It has the same layout as `struct iovec`, so a slice of `IoSlice`s can be
+/// handed to `writev` as is.
+#[unstable(feature = "iovec", issue = "0")]
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct IoSlice<'a> {
+    vec: linux::iovec,
+    _p: PhantomData<&'a [u8]>,
+}
+
+#[unstable(feature = "iovec", issue = "0")]
+unsafe impl<'a> Send for IoSlice<'a> {}
+
+#[unstable(feature = "iovec", issue = "0")]
+unsafe impl<'a> Sync for IoSlice<'a> {}
+
+impl<'a> IoSlice<'a> {
+    /// Creates a new `IoSlice` wrapping a byte slice.
+    #[unstable(feature = "iovec", issue = "0")]
+    #[inline]
+    pub fn new(buf: &'a [u8]) -> IoSlice<'a> {
+        IoSlice {
+            vec: linux::iovec {
+                iov_base: buf.as_ptr() as *mut _,
+                iov_len: buf.len(),
+            },
+            _p: PhantomData,
+        }
+    }
+}
+
+#[unstable(feature = "iovec", issue = "0")]
+impl<'a> Deref for IoSlice<'a> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        unsafe {
+            slice::from_raw_parts(self.vec.iov_base as *const u8,
+                                  self.vec.iov_len)
+        }
+    }
+}
+
+#[unstable(feature = "iovec", issue = "0")]
+impl<'a> fmt::Debug for IoSlice<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 pub trait BufRead: Read {
     #[stable(feature = "rust1", since = "1.0.0")]
@@ -320,6 +469,14 @@ impl Write for Stderr {
             n => Err(Error::from_raw_os_error(-n as i32)),
         }
     }
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        match unsafe {
+            linux::writev(STDERR, bufs.as_ptr() as *const _, iovcnt(bufs.len()))
+        } {
+            n if n >= 0 => Ok(n as usize),
+            n => Err(Error::from_raw_os_error(-n as i32)),
+        }
+    }
     fn flush(&mut self) -> io::Result<()> {
         Ok(())
     }
@@ -347,6 +504,14 @@ impl Read for Stdin {
             n => Err(Error::from_raw_os_error(-n as i32)),
         }
     }
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        match unsafe {
+            linux::readv(STDIN, bufs.as_ptr() as *const _, iovcnt(bufs.len()))
+        } {
+            n if n >= 0 => Ok(n as usize),
+            n => Err(Error::from_raw_os_error(-n as i32)),
+        }
+    }
 }
 
 #[stable(feature = "steed", since = "1.0.0")]
@@ -364,6 +529,14 @@ impl Write for Stdout {
             n => Err(Error::from_raw_os_error(-n as i32)),
         }
     }
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        match unsafe {
+            linux::writev(STDOUT, bufs.as_ptr() as *const _, iovcnt(bufs.len()))
+        } {
+            n if n >= 0 => Ok(n as usize),
+            n => Err(Error::from_raw_os_error(-n as i32)),
+        }
+    }
     fn flush(&mut self) -> io::Result<()> {
         Ok(())
     }
@@ -10,7 +10,10 @@
 
 #![allow(missing_copy_implementations)]
 
+use fs::File;
 use io::{self, Read, Write, ErrorKind, BufRead};
+use os::unix::io::{AsRawFd, RawFd};
+use sys;
 
 /// Copies the entire contents of a reader into a writer.
 ///
@@ -21,6 +24,9 @@ use io::{self, Read, Write, ErrorKind, BufRead};
 /// On success, the total number of bytes that were copied from
 /// `reader` to `writer` is returned.
 ///
+/// When one side is a pipe and the other a `File` or a standard stream, the
+/// data is moved with `splice` and never passes through userspace.
+///
 /// # Errors
 ///
 /// This function will return an error immediately if any call to `read` or
@@ -46,6 +52,12 @@ use io::{self, Read, Write, ErrorKind, BufRead};
 pub fn copy<R: ?Sized, W: ?Sized>(reader: &mut R, writer: &mut W) -> io::Result<u64>
     where R: Read, W: Write
 {
+    if let (Some(fd_in), Some(fd_out)) = ((*reader).copy_fd(),
+                                          (*writer).copy_fd()) {
+        if let Some(result) = sys::io::splice_copy(fd_in, fd_out) {
+            return result;
+        }
+    }
     let mut buf = [0; super::DEFAULT_BUF_SIZE];
     let mut written = 0;
     loop {
@@ -60,6 +72,37 @@ pub fn copy<R: ?Sized, W: ?Sized>(reader: &mut R, writer: &mut W) -> io::Result<
     }
 }
 
+// Finds the file descriptor behind the readers and writers that `copy` can
+// hand to the kernel. Only types that don't buffer in userspace qualify, as
+// anything buffered would be skipped or reordered.
+trait CopyFd {
+    fn copy_fd(&self) -> Option<RawFd>;
+}
+
+impl<T: ?Sized> CopyFd for T {
+    default fn copy_fd(&self) -> Option<RawFd> { None }
+}
+
+impl CopyFd for File {
+    fn copy_fd(&self) -> Option<RawFd> { Some(self.as_raw_fd()) }
+}
+
+impl<'a> CopyFd for &'a File {
+    fn copy_fd(&self) -> Option<RawFd> { Some(self.as_raw_fd()) }
+}
+
+impl CopyFd for io::Stdin {
+    fn copy_fd(&self) -> Option<RawFd> { Some(self.as_raw_fd()) }
+}
+
+impl CopyFd for io::Stdout {
+    fn copy_fd(&self) -> Option<RawFd> { Some(self.as_raw_fd()) }
+}
+
+impl CopyFd for io::Stderr {
+    fn copy_fd(&self) -> Option<RawFd> { Some(self.as_raw_fd()) }
+}
+
 /// A reader which is always at EOF.
 ///
 /// This struct is generally created by calling [`empty()`][empty]. Please see
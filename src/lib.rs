@@ -16,6 +16,7 @@
 #![feature(prelude_import)]
 #![feature(raw)]
 #![feature(slice_concat_ext)]
+#![feature(specialization)]
 #![feature(staged_api)]
 #![feature(try_from)]
 #![feature(unicode)]
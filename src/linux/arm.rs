@@ -8,10 +8,66 @@ pub const O_CLOEXEC: c_int = 0o02000000;
 pub const O_CREAT: c_int = 0o00000100;
 pub const O_DIRECTORY: c_int = 0o0100000;
 pub const O_EXCL: c_int = 0o00000200;
+pub const O_NOCTTY: c_int = 0o00000400;
+pub const O_NOFOLLOW: c_int = 0o00100000;
+pub const O_NONBLOCK: c_int = 0o00004000;
 pub const O_PATH: c_int = 0o010000000;
+pub const O_TMPFILE: c_int = 0o020000000 | O_DIRECTORY;
 pub const O_TRUNC: c_int = 0o00001000;
 
 pub const FIOCLEX: c_uint = 0x5451;
+pub const FICLONE: c_uint = 0x40049409;
+pub const FIONBIO: c_uint = 0x5421;
+pub const TCGETS: c_uint = 0x5401;
+pub const TCSETS: c_uint = 0x5402;
+pub const TCSETSW: c_uint = 0x5403;
+pub const TCSETSF: c_uint = 0x5404;
+pub const TIOCGWINSZ: c_uint = 0x5413;
+pub const TIOCSWINSZ: c_uint = 0x5414;
+pub const TIOCGPTN: c_uint = 0x80045430;
+pub const TIOCSPTLCK: c_uint = 0x40045431;
+pub const TIOCGPTPEER: c_uint = 0x5441;
+
+pub const MAP_ANONYMOUS: c_int = 0x20;
+
+// include/uapi/asm-generic/resource.h
+pub const RLIMIT_RSS: c_uint = 5;
+pub const RLIMIT_NPROC: c_uint = 6;
+pub const RLIMIT_NOFILE: c_uint = 7;
+pub const RLIMIT_MEMLOCK: c_uint = 8;
+pub const RLIMIT_AS: c_uint = 9;
+
+// include/uapi/linux/audit.h
+pub const AUDIT_ARCH_ARM: u32 = 0x40000028;
+pub const AUDIT_ARCH_ARMEB: u32 = 0x00000028;
+
+// arch/arm/include/asm/seccomp.h
+#[cfg(target_endian = "little")]
+pub const SECCOMP_ARCH_NATIVE: u32 = AUDIT_ARCH_ARM;
+#[cfg(target_endian = "big")]
+pub const SECCOMP_ARCH_NATIVE: u32 = AUDIT_ARCH_ARMEB;
+
+// include/uapi/asm-generic/termbits.h
+pub const IGNBRK: tcflag_t = 0o000001;
+pub const BRKINT: tcflag_t = 0o000002;
+pub const PARMRK: tcflag_t = 0o000010;
+pub const ISTRIP: tcflag_t = 0o000040;
+pub const INLCR: tcflag_t = 0o000100;
+pub const IGNCR: tcflag_t = 0o000200;
+pub const ICRNL: tcflag_t = 0o000400;
+pub const IXON: tcflag_t = 0o002000;
+pub const OPOST: tcflag_t = 0o000001;
+pub const CSIZE: tcflag_t = 0o000060;
+pub const CS8: tcflag_t = 0o000060;
+pub const PARENB: tcflag_t = 0o000400;
+pub const ISIG: tcflag_t = 0o000001;
+pub const ICANON: tcflag_t = 0o000002;
+pub const ECHO: tcflag_t = 0o000010;
+pub const ECHONL: tcflag_t = 0o000100;
+pub const IEXTEN: tcflag_t = 0o100000;
+pub const VTIME: usize = 5;
+pub const VMIN: usize = 6;
+pub const NCCS: usize = 19;
 
 // include/linux/types.h
 pub type ino_t = __kernel_ino_t;
@@ -44,3 +100,15 @@ pub struct stat64 {
 }
 
 pub type blksize_t = i32;
+
+// include/uapi/asm-generic/termbits.h
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct termios {
+    pub c_iflag: tcflag_t,
+    pub c_oflag: tcflag_t,
+    pub c_cflag: tcflag_t,
+    pub c_lflag: tcflag_t,
+    pub c_line: cc_t,
+    pub c_cc: [cc_t; NCCS],
+}
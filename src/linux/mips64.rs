@@ -8,10 +8,66 @@ pub const O_CLOEXEC: c_int = 0o02000000;
 pub const O_CREAT: c_int = 0x0100;
 pub const O_DIRECTORY: c_int = 0o00200000;
 pub const O_EXCL: c_int = 0x0400;
+pub const O_NOCTTY: c_int = 0x0800;
+pub const O_NOFOLLOW: c_int = 0o00400000;
+pub const O_NONBLOCK: c_int = 0x0080;
 pub const O_PATH: c_int = 0o010000000;
+pub const O_TMPFILE: c_int = 0o020000000 | O_DIRECTORY;
 pub const O_TRUNC: c_int = 0x0200;
 
 pub const FIOCLEX: c_uint = 0x6601;
+pub const FICLONE: c_uint = 0x80049409;
+pub const FIONBIO: c_uint = 0x667e;
+pub const TCGETS: c_uint = 0x540d;
+pub const TCSETS: c_uint = 0x540e;
+pub const TCSETSW: c_uint = 0x540f;
+pub const TCSETSF: c_uint = 0x5410;
+pub const TIOCGWINSZ: c_uint = 0x40087468;
+pub const TIOCSWINSZ: c_uint = 0x80087467;
+pub const TIOCGPTN: c_uint = 0x40045430;
+pub const TIOCSPTLCK: c_uint = 0x80045431;
+pub const TIOCGPTPEER: c_uint = 0x20005441;
+
+pub const MAP_ANONYMOUS: c_int = 0x800;
+
+// arch/mips/include/uapi/asm/resource.h
+pub const RLIMIT_NOFILE: c_uint = 5;
+pub const RLIMIT_AS: c_uint = 6;
+pub const RLIMIT_RSS: c_uint = 7;
+pub const RLIMIT_NPROC: c_uint = 8;
+pub const RLIMIT_MEMLOCK: c_uint = 9;
+
+// include/uapi/linux/audit.h
+pub const AUDIT_ARCH_MIPS64: u32 = 0x80000008;
+pub const AUDIT_ARCH_MIPSEL64: u32 = 0xc0000008;
+
+// arch/mips/include/asm/seccomp.h, for the n64 ABI
+#[cfg(target_endian = "big")]
+pub const SECCOMP_ARCH_NATIVE: u32 = AUDIT_ARCH_MIPS64;
+#[cfg(target_endian = "little")]
+pub const SECCOMP_ARCH_NATIVE: u32 = AUDIT_ARCH_MIPSEL64;
+
+// arch/mips/include/uapi/asm/termbits.h
+pub const IGNBRK: tcflag_t = 0o000001;
+pub const BRKINT: tcflag_t = 0o000002;
+pub const PARMRK: tcflag_t = 0o000010;
+pub const ISTRIP: tcflag_t = 0o000040;
+pub const INLCR: tcflag_t = 0o000100;
+pub const IGNCR: tcflag_t = 0o000200;
+pub const ICRNL: tcflag_t = 0o000400;
+pub const IXON: tcflag_t = 0o002000;
+pub const OPOST: tcflag_t = 0o000001;
+pub const CSIZE: tcflag_t = 0o000060;
+pub const CS8: tcflag_t = 0o000060;
+pub const PARENB: tcflag_t = 0o000400;
+pub const ISIG: tcflag_t = 0o000001;
+pub const ICANON: tcflag_t = 0o000002;
+pub const ECHO: tcflag_t = 0o000010;
+pub const ECHONL: tcflag_t = 0o000100;
+pub const IEXTEN: tcflag_t = 0o000400;
+pub const VMIN: usize = 4;
+pub const VTIME: usize = 5;
+pub const NCCS: usize = 23;
 
 #[derive(Clone, Copy)]
 #[repr(C)]
@@ -39,3 +95,15 @@ pub struct stat64 {
 }
 
 pub type blksize_t = i64;
+
+// arch/mips/include/uapi/asm/termbits.h
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct termios {
+    pub c_iflag: tcflag_t,
+    pub c_oflag: tcflag_t,
+    pub c_cflag: tcflag_t,
+    pub c_lflag: tcflag_t,
+    pub c_line: cc_t,
+    pub c_cc: [cc_t; NCCS],
+}
@@ -43,21 +43,117 @@ pub use self::arch::*;
 pub use self::types::*;
 
 // include/uapi/linux/fcntl.h
+pub const AT_EMPTY_PATH: c_int = 0x1000;
 pub const AT_FDCWD: c_int = -100;
 pub const AT_REMOVEDIR: c_int = 0x200;
+pub const AT_SYMLINK_FOLLOW: c_int = 0x400;
 pub const AT_SYMLINK_NOFOLLOW: c_int = 0x100;
+pub const F_ADD_SEALS: c_uint = F_LINUX_SPECIFIC_BASE + 9;
 pub const F_DUPFD_CLOEXEC: c_uint = F_LINUX_SPECIFIC_BASE + 6;
+pub const F_GET_SEALS: c_uint = F_LINUX_SPECIFIC_BASE + 10;
+pub const F_SEAL_SEAL: c_uint = 0x0001;
+pub const F_SEAL_SHRINK: c_uint = 0x0002;
+pub const F_SEAL_GROW: c_uint = 0x0004;
+pub const F_SEAL_WRITE: c_uint = 0x0008;
 
 // include/uapi/asm-generic/fcntl.h
 pub const F_DUPFD: c_uint = 0;
 pub const F_GETFL: c_uint = 3;
 pub const F_LINUX_SPECIFIC_BASE: c_uint = 1024;
+pub const F_OFD_GETLK: c_uint = 36;
+pub const F_OFD_SETLK: c_uint = 37;
+pub const F_OFD_SETLKW: c_uint = 38;
+pub const F_RDLCK: c_short = 0;
+pub const F_WRLCK: c_short = 1;
+pub const F_UNLCK: c_short = 2;
+pub const LOCK_SH: c_uint = 1;
+pub const LOCK_EX: c_uint = 2;
+pub const LOCK_NB: c_uint = 4;
+pub const LOCK_UN: c_uint = 8;
 pub const O_ACCMODE: c_int = 0o00000003;
 pub const O_LARGEFILE: c_int = 0o00100000;
 pub const O_RDONLY: c_int = 0o00000000;
 pub const O_RDWR: c_int = 0o00000002;
 pub const O_WRONLY: c_int = 0o00000001;
 
+// include/uapi/linux/auxvec.h
+pub const AT_NULL: usize = 0;
+pub const AT_PAGESZ: usize = 6;
+
+// include/uapi/asm-generic/mman-common.h
+pub const MADV_NORMAL: c_int = 0;
+pub const MADV_RANDOM: c_int = 1;
+pub const MADV_SEQUENTIAL: c_int = 2;
+pub const MADV_WILLNEED: c_int = 3;
+pub const MADV_DONTNEED: c_int = 4;
+pub const MAP_SHARED: c_int = 0x01;
+pub const MAP_PRIVATE: c_int = 0x02;
+pub const MS_ASYNC: c_int = 1;
+pub const MS_SYNC: c_int = 4;
+pub const PROT_READ: c_int = 0x1;
+pub const PROT_WRITE: c_int = 0x2;
+
+// include/uapi/linux/inotify.h
+pub const IN_ACCESS: u32 = 0x00000001;
+pub const IN_MODIFY: u32 = 0x00000002;
+pub const IN_ATTRIB: u32 = 0x00000004;
+pub const IN_CLOSE_WRITE: u32 = 0x00000008;
+pub const IN_CLOSE_NOWRITE: u32 = 0x00000010;
+pub const IN_OPEN: u32 = 0x00000020;
+pub const IN_MOVED_FROM: u32 = 0x00000040;
+pub const IN_MOVED_TO: u32 = 0x00000080;
+pub const IN_CREATE: u32 = 0x00000100;
+pub const IN_DELETE: u32 = 0x00000200;
+pub const IN_DELETE_SELF: u32 = 0x00000400;
+pub const IN_MOVE_SELF: u32 = 0x00000800;
+pub const IN_UNMOUNT: u32 = 0x00002000;
+pub const IN_Q_OVERFLOW: u32 = 0x00004000;
+pub const IN_IGNORED: u32 = 0x00008000;
+pub const IN_ONLYDIR: u32 = 0x01000000;
+pub const IN_DONT_FOLLOW: u32 = 0x02000000;
+pub const IN_EXCL_UNLINK: u32 = 0x04000000;
+pub const IN_MASK_ADD: u32 = 0x20000000;
+pub const IN_ISDIR: u32 = 0x40000000;
+pub const IN_ONESHOT: u32 = 0x80000000;
+pub const IN_CLOEXEC: c_int = O_CLOEXEC;
+pub const IN_NONBLOCK: c_int = O_NONBLOCK;
+
+// include/uapi/linux/eventpoll.h
+pub const EPOLL_CLOEXEC: c_int = O_CLOEXEC;
+pub const EPOLL_CTL_ADD: c_int = 1;
+pub const EPOLL_CTL_DEL: c_int = 2;
+pub const EPOLL_CTL_MOD: c_int = 3;
+pub const EPOLLIN: u32 = 0x00000001;
+pub const EPOLLPRI: u32 = 0x00000002;
+pub const EPOLLOUT: u32 = 0x00000004;
+pub const EPOLLERR: u32 = 0x00000008;
+pub const EPOLLHUP: u32 = 0x00000010;
+pub const EPOLLRDHUP: u32 = 0x00002000;
+pub const EPOLLEXCLUSIVE: u32 = 1 << 28;
+pub const EPOLLWAKEUP: u32 = 1 << 29;
+pub const EPOLLONESHOT: u32 = 1 << 30;
+pub const EPOLLET: u32 = 1 << 31;
+
+// include/uapi/asm-generic/poll.h
+pub const POLLIN: c_short = 0x0001;
+pub const POLLPRI: c_short = 0x0002;
+pub const POLLOUT: c_short = 0x0004;
+pub const POLLERR: c_short = 0x0008;
+pub const POLLHUP: c_short = 0x0010;
+pub const POLLNVAL: c_short = 0x0020;
+
+// include/uapi/linux/uio.h
+pub const UIO_MAXIOV: usize = 1024;
+
+// include/linux/splice.h
+pub const SPLICE_F_MOVE: c_uint = 0x01;
+pub const SPLICE_F_NONBLOCK: c_uint = 0x02;
+pub const SPLICE_F_MORE: c_uint = 0x04;
+
+// include/uapi/linux/memfd.h
+pub const MFD_CLOEXEC: c_uint = 0x0001;
+pub const MFD_ALLOW_SEALING: c_uint = 0x0002;
+
 // include/uapi/linux/stat.h
 pub const S_IFMT: c_uint = 0o00170000;
 pub const S_IFSOCK: c_uint = 0o0140000;
@@ -68,16 +164,151 @@ pub const S_IFDIR: c_uint = 0o0040000;
 pub const S_IFCHR: c_uint = 0o0020000;
 pub const S_IFIFO: c_uint = 0o0010000;
 
+// include/uapi/linux/falloc.h
+pub const FALLOC_FL_KEEP_SIZE: c_int = 0x01;
+pub const FALLOC_FL_PUNCH_HOLE: c_int = 0x02;
+pub const FALLOC_FL_ZERO_RANGE: c_int = 0x10;
+
+// include/uapi/linux/stat.h
+pub const UTIME_NOW: c_long = (1 << 30) - 1;
+pub const UTIME_OMIT: c_long = (1 << 30) - 2;
+
 // include/uapi/linux/time.h
+pub const CLOCK_BOOTTIME: clockid_t = 7;
 pub const CLOCK_MONOTONIC: clockid_t = 1;
 pub const CLOCK_REALTIME: clockid_t = 0;
 
+// include/uapi/linux/eventfd.h
+pub const EFD_CLOEXEC: c_int = O_CLOEXEC;
+pub const EFD_NONBLOCK: c_int = O_NONBLOCK;
+pub const EFD_SEMAPHORE: c_int = 1;
+
+// include/uapi/linux/timerfd.h
+pub const TFD_CLOEXEC: c_int = O_CLOEXEC;
+pub const TFD_NONBLOCK: c_int = O_NONBLOCK;
+pub const TFD_TIMER_ABSTIME: c_int = 1 << 0;
+
+// include/uapi/linux/stat.h
+pub const STATX_BASIC_STATS: c_uint = 0x000007ff;
+
+// include/uapi/linux/io_uring.h
+pub const IORING_OP_FSYNC: u8 = 3;
+pub const IORING_OP_ASYNC_CANCEL: u8 = 14;
+pub const IORING_OP_OPENAT: u8 = 18;
+pub const IORING_OP_CLOSE: u8 = 19;
+pub const IORING_OP_STATX: u8 = 21;
+pub const IORING_OP_READ: u8 = 22;
+pub const IORING_OP_WRITE: u8 = 23;
+pub const IORING_FSYNC_DATASYNC: u32 = 1 << 0;
+pub const IORING_OFF_SQ_RING: u64 = 0;
+pub const IORING_OFF_CQ_RING: u64 = 0x8000000;
+pub const IORING_OFF_SQES: u64 = 0x10000000;
+pub const IORING_ENTER_GETEVENTS: c_uint = 1 << 0;
+pub const IORING_FEAT_SINGLE_MMAP: u32 = 1 << 0;
+pub const IORING_FEAT_RW_CUR_POS: u32 = 1 << 3;
+
+// include/uapi/asm-generic/resource.h
+pub const RLIMIT_CPU: c_uint = 0;
+pub const RLIMIT_FSIZE: c_uint = 1;
+pub const RLIMIT_DATA: c_uint = 2;
+pub const RLIMIT_STACK: c_uint = 3;
+pub const RLIMIT_CORE: c_uint = 4;
+pub const RLIMIT_LOCKS: c_uint = 10;
+pub const RLIMIT_SIGPENDING: c_uint = 11;
+pub const RLIMIT_MSGQUEUE: c_uint = 12;
+pub const RLIMIT_NICE: c_uint = 13;
+pub const RLIMIT_RTPRIO: c_uint = 14;
+pub const RLIMIT_RTTIME: c_uint = 15;
+
+// include/uapi/linux/resource.h
+pub const RLIM64_INFINITY: u64 = !0;
+pub const RUSAGE_SELF: c_int = 0;
+pub const RUSAGE_CHILDREN: c_int = -1;
+pub const RUSAGE_THREAD: c_int = 1;
+
+// include/uapi/linux/prctl.h
+pub const PR_SET_PDEATHSIG: c_int = 1;
+pub const PR_GET_PDEATHSIG: c_int = 2;
+pub const PR_GET_DUMPABLE: c_int = 3;
+pub const PR_SET_DUMPABLE: c_int = 4;
+pub const PR_SET_NAME: c_int = 15;
+pub const PR_GET_NAME: c_int = 16;
+pub const PR_SET_TIMERSLACK: c_int = 29;
+pub const PR_GET_TIMERSLACK: c_int = 30;
+pub const PR_SET_CHILD_SUBREAPER: c_int = 36;
+pub const PR_GET_CHILD_SUBREAPER: c_int = 37;
+pub const PR_SET_NO_NEW_PRIVS: c_int = 38;
+pub const PR_GET_NO_NEW_PRIVS: c_int = 39;
+
+// include/uapi/linux/bpf_common.h
+pub const BPF_LD: u16 = 0x00;
+pub const BPF_ALU: u16 = 0x04;
+pub const BPF_JMP: u16 = 0x05;
+pub const BPF_RET: u16 = 0x06;
+pub const BPF_W: u16 = 0x00;
+pub const BPF_ABS: u16 = 0x20;
+pub const BPF_AND: u16 = 0x50;
+pub const BPF_JEQ: u16 = 0x10;
+pub const BPF_JGT: u16 = 0x20;
+pub const BPF_JGE: u16 = 0x30;
+pub const BPF_K: u16 = 0x00;
+
+// include/uapi/linux/seccomp.h
+pub const SECCOMP_SET_MODE_FILTER: c_uint = 1;
+pub const SECCOMP_RET_KILL_PROCESS: u32 = 0x80000000;
+pub const SECCOMP_RET_KILL_THREAD: u32 = 0x00000000;
+pub const SECCOMP_RET_TRAP: u32 = 0x00030000;
+pub const SECCOMP_RET_ERRNO: u32 = 0x00050000;
+pub const SECCOMP_RET_TRACE: u32 = 0x7ff00000;
+pub const SECCOMP_RET_LOG: u32 = 0x7ffc0000;
+pub const SECCOMP_RET_ALLOW: u32 = 0x7fff0000;
+pub const SECCOMP_RET_DATA: u32 = 0x0000ffff;
+
+// include/uapi/linux/sched.h
+pub const CLONE_NEWTIME: c_int = 0x00000080;
+pub const CLONE_NEWNS: c_int = 0x00020000;
+pub const CLONE_NEWCGROUP: c_int = 0x02000000;
+pub const CLONE_NEWUTS: c_int = 0x04000000;
+pub const CLONE_NEWIPC: c_int = 0x08000000;
+pub const CLONE_NEWUSER: c_int = 0x10000000;
+pub const CLONE_NEWPID: c_int = 0x20000000;
+pub const CLONE_NEWNET: c_int = 0x40000000;
+
+// include/uapi/linux/mount.h
+pub const MS_RDONLY: c_ulong = 1;
+pub const MS_NOSUID: c_ulong = 2;
+pub const MS_NODEV: c_ulong = 4;
+pub const MS_NOEXEC: c_ulong = 8;
+pub const MS_REMOUNT: c_ulong = 32;
+pub const MS_NOATIME: c_ulong = 1024;
+pub const MS_NODIRATIME: c_ulong = 2048;
+pub const MS_BIND: c_ulong = 4096;
+pub const MS_MOVE: c_ulong = 8192;
+pub const MS_REC: c_ulong = 16384;
+pub const MS_UNBINDABLE: c_ulong = 1 << 17;
+pub const MS_PRIVATE: c_ulong = 1 << 18;
+pub const MS_SLAVE: c_ulong = 1 << 19;
+pub const MS_SHARED: c_ulong = 1 << 20;
+pub const MS_RELATIME: c_ulong = 1 << 21;
+pub const MS_STRICTATIME: c_ulong = 1 << 24;
+
+// include/linux/fs.h
+pub const MNT_FORCE: c_int = 0x00000001;
+pub const MNT_DETACH: c_int = 0x00000002;
+pub const MNT_EXPIRE: c_int = 0x00000004;
+pub const UMOUNT_NOFOLLOW: c_int = 0x00000008;
+
 // include/uapi/linux/fs.h
+pub const RENAME_NOREPLACE: c_uint = 1 << 0;
+pub const RENAME_EXCHANGE: c_uint = 1 << 1;
 pub const SEEK_SET: c_uint = 0;
 pub const SEEK_CUR: c_uint = 1;
 pub const SEEK_END: c_uint = 2;
+pub const SEEK_DATA: c_uint = 3;
+pub const SEEK_HOLE: c_uint = 4;
 
 // include/linux/fs.h
+pub const DT_UNKNOWN: c_uchar = 0;
 pub const DT_FIFO: c_uchar = 1;
 pub const DT_CHR: c_uchar = 2;
 pub const DT_DIR: c_uchar = 4;
@@ -108,6 +339,82 @@ pub unsafe fn exit_group(code: c_int) -> ! {
     intrinsics::unreachable()
 }
 
+// kernel/fork.c
+#[inline(always)]
+pub unsafe fn unshare(unshare_flags: c_ulong) -> ssize_t {
+    syscall!(UNSHARE, unshare_flags) as ssize_t
+}
+
+// kernel/nsproxy.c
+#[inline(always)]
+pub unsafe fn setns(fd: c_int, nstype: c_int) -> ssize_t {
+    syscall!(SETNS, fd, nstype) as ssize_t
+}
+
+// fs/namespace.c
+#[inline(always)]
+pub unsafe fn mount(dev_name: *const c_char,
+                    dir_name: *const c_char,
+                    type_: *const c_char,
+                    flags: c_ulong,
+                    data: *const c_char)
+                    -> ssize_t {
+    syscall!(MOUNT, dev_name, dir_name, type_, flags, data) as ssize_t
+}
+
+// fs/namespace.c
+#[inline(always)]
+pub unsafe fn umount2(name: *const c_char, flags: c_int) -> ssize_t {
+    syscall!(UMOUNT2, name, flags) as ssize_t
+}
+
+// fs/namespace.c
+#[inline(always)]
+pub unsafe fn pivot_root(new_root: *const c_char, put_old: *const c_char)
+                         -> ssize_t {
+    syscall!(PIVOT_ROOT, new_root, put_old) as ssize_t
+}
+
+// fs/open.c
+#[inline(always)]
+pub unsafe fn chroot(filename: *const c_char) -> ssize_t {
+    syscall!(CHROOT, filename) as ssize_t
+}
+
+// kernel/sys.c
+#[inline(always)]
+pub unsafe fn prctl(option: c_int,
+                    arg2: c_ulong,
+                    arg3: c_ulong,
+                    arg4: c_ulong,
+                    arg5: c_ulong)
+                    -> ssize_t {
+    syscall!(PRCTL, option, arg2, arg3, arg4, arg5) as ssize_t
+}
+
+// kernel/seccomp.c
+#[inline(always)]
+pub unsafe fn seccomp(op: c_uint, flags: c_uint, uargs: *const sock_fprog)
+                      -> ssize_t {
+    syscall!(SECCOMP, op, flags, uargs) as ssize_t
+}
+
+// kernel/sys.c
+#[inline(always)]
+pub unsafe fn prlimit64(pid: pid_t,
+                        resource: c_uint,
+                        new_rlim: *const rlimit64,
+                        old_rlim: *mut rlimit64)
+                        -> ssize_t {
+    syscall!(PRLIMIT64, pid, resource, new_rlim, old_rlim) as ssize_t
+}
+
+// kernel/sys.c
+#[inline(always)]
+pub unsafe fn getrusage(who: c_int, ru: *mut rusage) -> ssize_t {
+    syscall!(GETRUSAGE, who, ru) as ssize_t
+}
+
 // fs/open.c
 #[inline(always)]
 pub unsafe fn open(filename: *const c_char,
@@ -117,6 +424,16 @@ pub unsafe fn open(filename: *const c_char,
     syscall!(OPENAT, AT_FDCWD, filename, flags, mode) as ssize_t
 }
 
+// fs/open.c
+#[inline(always)]
+pub unsafe fn openat(dfd: c_int,
+                     filename: *const c_char,
+                     flags: c_int,
+                     mode: umode_t)
+                     -> ssize_t {
+    syscall!(OPENAT, dfd, filename, flags, mode) as ssize_t
+}
+
 // fs/open.c
 #[inline(always)]
 pub unsafe fn chmod(filename: *const c_char,
@@ -125,6 +442,44 @@ pub unsafe fn chmod(filename: *const c_char,
     syscall!(FCHMODAT, AT_FDCWD, filename, mode, 0) as ssize_t
 }
 
+// fs/open.c
+#[inline(always)]
+pub unsafe fn fchownat(dfd: c_int,
+                       filename: *const c_char,
+                       user: uid_t,
+                       group: gid_t,
+                       flag: c_int)
+                       -> ssize_t {
+    syscall!(FCHOWNAT, dfd, filename, user, group, flag) as ssize_t
+}
+
+// fs/open.c
+#[inline(always)]
+pub unsafe fn fchown(fd: c_uint, user: uid_t, group: gid_t) -> ssize_t {
+    // The plain `fchown` of these architectures only takes 16-bit IDs.
+    #[cfg(any(target_arch = "arm", target_arch = "x86"))]
+    #[inline(always)]
+    unsafe fn fchown(fd: c_uint, user: uid_t, group: gid_t) -> ssize_t {
+        syscall!(FCHOWN32, fd, user, group) as ssize_t
+    }
+    #[cfg(not(any(target_arch = "arm", target_arch = "x86")))]
+    #[inline(always)]
+    unsafe fn fchown(fd: c_uint, user: uid_t, group: gid_t) -> ssize_t {
+        syscall!(FCHOWN, fd, user, group) as ssize_t
+    }
+    fchown(fd, user, group)
+}
+
+// fs/utimes.c
+#[inline(always)]
+pub unsafe fn utimensat(dfd: c_int,
+                        filename: *const c_char,
+                        utimes: *const timespec,
+                        flags: c_int)
+                        -> ssize_t {
+    syscall!(UTIMENSAT, dfd, filename, utimes, flags) as ssize_t
+}
+
 // fs/read_write.c
 #[inline(always)]
 pub unsafe fn read(fd: c_int, buffer: *mut c_char, count: size_t) -> ssize_t {
@@ -224,6 +579,129 @@ pub unsafe fn pwrite64(fd: c_int,
     pwrite64(fd, buffer, count, pos)
 }
 
+// fs/read_write.c
+#[inline(always)]
+pub unsafe fn readv(fd: c_int, vec: *const iovec, vlen: c_int) -> ssize_t {
+    syscall!(READV, fd, vec, vlen) as ssize_t
+}
+
+// fs/read_write.c
+#[inline(always)]
+pub unsafe fn writev(fd: c_int, vec: *const iovec, vlen: c_int) -> ssize_t {
+    syscall!(WRITEV, fd, vec, vlen) as ssize_t
+}
+
+// fs/read_write.c
+//
+// Unlike `pread64`, the kernel always takes the offset as two longs, low
+// word first and without padding, regardless of endianness or architecture.
+// On 64-bit the high word is ignored.
+#[inline(always)]
+pub unsafe fn preadv(fd: c_int,
+                     vec: *const iovec,
+                     vlen: c_int,
+                     pos: loff_t)
+                     -> ssize_t {
+    #[cfg(target_pointer_width = "32")]
+    #[inline(always)]
+    unsafe fn preadv(fd: c_int,
+                     vec: *const iovec,
+                     vlen: c_int,
+                     pos: loff_t)
+                     -> ssize_t {
+        syscall!(PREADV, fd, vec, vlen, pos & 0xffff_ffff, pos >> 32) as ssize_t
+    }
+    #[cfg(target_pointer_width = "64")]
+    #[inline(always)]
+    unsafe fn preadv(fd: c_int,
+                     vec: *const iovec,
+                     vlen: c_int,
+                     pos: loff_t)
+                     -> ssize_t {
+        syscall!(PREADV, fd, vec, vlen, pos, 0) as ssize_t
+    }
+    preadv(fd, vec, vlen, pos)
+}
+
+// fs/read_write.c
+//
+// See `preadv` for how the offset is passed.
+#[inline(always)]
+pub unsafe fn pwritev(fd: c_int,
+                      vec: *const iovec,
+                      vlen: c_int,
+                      pos: loff_t)
+                      -> ssize_t {
+    #[cfg(target_pointer_width = "32")]
+    #[inline(always)]
+    unsafe fn pwritev(fd: c_int,
+                      vec: *const iovec,
+                      vlen: c_int,
+                      pos: loff_t)
+                      -> ssize_t {
+        syscall!(PWRITEV, fd, vec, vlen, pos & 0xffff_ffff, pos >> 32) as ssize_t
+    }
+    #[cfg(target_pointer_width = "64")]
+    #[inline(always)]
+    unsafe fn pwritev(fd: c_int,
+                      vec: *const iovec,
+                      vlen: c_int,
+                      pos: loff_t)
+                      -> ssize_t {
+        syscall!(PWRITEV, fd, vec, vlen, pos, 0) as ssize_t
+    }
+    pwritev(fd, vec, vlen, pos)
+}
+
+// fs/select.c
+//
+// Like `epoll_pwait`, the signal mask is left alone.
+#[inline(always)]
+pub unsafe fn ppoll(ufds: *mut pollfd,
+                    nfds: c_uint,
+                    tsp: *mut timespec)
+                    -> ssize_t {
+    syscall!(PPOLL, ufds, nfds, tsp, 0, 0) as ssize_t
+}
+
+// fs/pipe.c
+#[inline(always)]
+pub unsafe fn pipe2(fds: *mut c_int, flags: c_int) -> ssize_t {
+    syscall!(PIPE2, fds, flags) as ssize_t
+}
+
+// fs/splice.c
+#[inline(always)]
+pub unsafe fn splice(fd_in: c_int,
+                     off_in: *mut loff_t,
+                     fd_out: c_int,
+                     off_out: *mut loff_t,
+                     len: size_t,
+                     flags: c_uint)
+                     -> ssize_t {
+    syscall!(SPLICE, fd_in, off_in, fd_out, off_out, len, flags) as ssize_t
+}
+
+// fs/splice.c
+#[inline(always)]
+pub unsafe fn tee(fd_in: c_int,
+                  fd_out: c_int,
+                  len: size_t,
+                  flags: c_uint)
+                  -> ssize_t {
+    syscall!(TEE, fd_in, fd_out, len, flags) as ssize_t
+}
+
+// fs/splice.c
+#[inline(always)]
+pub unsafe fn vmsplice(fd: c_int,
+                       iov: *const iovec,
+                       nr_segs: c_ulong,
+                       flags: c_uint)
+                       -> ssize_t {
+    syscall!(VMSPLICE, fd, iov, nr_segs, flags) as ssize_t
+}
+
 // fs/read_write.c
 #[inline(always)]
 pub unsafe fn _llseek(fd: c_int,
@@ -259,6 +737,47 @@ pub unsafe fn _llseek(fd: c_int,
     _llseek(fd, offset, result, whence)
 }
 
+// fs/read_write.c
+#[inline(always)]
+pub unsafe fn sendfile64(out_fd: c_int,
+                         in_fd: c_int,
+                         offset: *mut loff_t,
+                         count: size_t)
+                         -> ssize_t {
+    #[cfg(target_pointer_width = "32")]
+    #[inline(always)]
+    unsafe fn sendfile64(out_fd: c_int,
+                         in_fd: c_int,
+                         offset: *mut loff_t,
+                         count: size_t)
+                         -> ssize_t {
+        syscall!(SENDFILE64, out_fd, in_fd, offset, count) as ssize_t
+    }
+    #[cfg(target_pointer_width = "64")]
+    #[inline(always)]
+    unsafe fn sendfile64(out_fd: c_int,
+                         in_fd: c_int,
+                         offset: *mut loff_t,
+                         count: size_t)
+                         -> ssize_t {
+        syscall!(SENDFILE, out_fd, in_fd, offset, count) as ssize_t
+    }
+    sendfile64(out_fd, in_fd, offset, count)
+}
+
+// fs/read_write.c
+#[inline(always)]
+pub unsafe fn copy_file_range(fd_in: c_int,
+                              off_in: *mut loff_t,
+                              fd_out: c_int,
+                              off_out: *mut loff_t,
+                              len: size_t,
+                              flags: c_uint)
+                              -> ssize_t {
+    syscall!(COPY_FILE_RANGE, fd_in, off_in, fd_out, off_out, len, flags)
+        as ssize_t
+}
+
 // fs/open.c
 #[inline(always)]
 pub unsafe fn ftruncate64(fd: c_int, length: loff_t) -> ssize_t {
@@ -280,6 +799,321 @@ pub unsafe fn ftruncate64(fd: c_int, length: loff_t) -> ssize_t {
     ftruncate64(fd, length)
 }
 
+// fs/open.c
+#[inline(always)]
+pub unsafe fn fallocate(fd: c_int,
+                        mode: c_int,
+                        offset: loff_t,
+                        len: loff_t)
+                        -> ssize_t {
+    #[cfg(target_pointer_width = "32")]
+    #[inline(always)]
+    unsafe fn fallocate(fd: c_int,
+                        mode: c_int,
+                        offset: loff_t,
+                        len: loff_t)
+                        -> ssize_t {
+        syscall!(FALLOCATE,
+                 fd,
+                 mode,
+                 high(offset),
+                 low(offset),
+                 high(len),
+                 low(len)) as ssize_t
+    }
+    #[cfg(target_pointer_width = "64")]
+    #[inline(always)]
+    unsafe fn fallocate(fd: c_int,
+                        mode: c_int,
+                        offset: loff_t,
+                        len: loff_t)
+                        -> ssize_t {
+        syscall!(FALLOCATE, fd, mode, offset, len) as ssize_t
+    }
+    fallocate(fd, mode, offset, len)
+}
+
+// fs/xattr.c
+#[inline(always)]
+pub unsafe fn getxattr(pathname: *const c_char,
+                       name: *const c_char,
+                       value: *mut c_char,
+                       size: size_t)
+                       -> ssize_t {
+    syscall!(GETXATTR, pathname, name, value, size) as ssize_t
+}
+
+// fs/xattr.c
+#[inline(always)]
+pub unsafe fn setxattr(pathname: *const c_char,
+                       name: *const c_char,
+                       value: *const c_char,
+                       size: size_t,
+                       flags: c_int)
+                       -> ssize_t {
+    syscall!(SETXATTR, pathname, name, value, size, flags) as ssize_t
+}
+
+// fs/xattr.c
+#[inline(always)]
+pub unsafe fn listxattr(pathname: *const c_char,
+                        list: *mut c_char,
+                        size: size_t)
+                        -> ssize_t {
+    syscall!(LISTXATTR, pathname, list, size) as ssize_t
+}
+
+// fs/xattr.c
+#[inline(always)]
+pub unsafe fn removexattr(pathname: *const c_char, name: *const c_char) -> ssize_t {
+    syscall!(REMOVEXATTR, pathname, name) as ssize_t
+}
+
+// fs/xattr.c
+#[inline(always)]
+pub unsafe fn lgetxattr(pathname: *const c_char,
+                        name: *const c_char,
+                        value: *mut c_char,
+                        size: size_t)
+                        -> ssize_t {
+    syscall!(LGETXATTR, pathname, name, value, size) as ssize_t
+}
+
+// fs/xattr.c
+#[inline(always)]
+pub unsafe fn lsetxattr(pathname: *const c_char,
+                        name: *const c_char,
+                        value: *const c_char,
+                        size: size_t,
+                        flags: c_int)
+                        -> ssize_t {
+    syscall!(LSETXATTR, pathname, name, value, size, flags) as ssize_t
+}
+
+// fs/xattr.c
+#[inline(always)]
+pub unsafe fn llistxattr(pathname: *const c_char,
+                         list: *mut c_char,
+                         size: size_t)
+                         -> ssize_t {
+    syscall!(LLISTXATTR, pathname, list, size) as ssize_t
+}
+
+// fs/xattr.c
+#[inline(always)]
+pub unsafe fn lremovexattr(pathname: *const c_char, name: *const c_char) -> ssize_t {
+    syscall!(LREMOVEXATTR, pathname, name) as ssize_t
+}
+
+// fs/xattr.c
+#[inline(always)]
+pub unsafe fn fgetxattr(fd: c_int,
+                        name: *const c_char,
+                        value: *mut c_char,
+                        size: size_t)
+                        -> ssize_t {
+    syscall!(FGETXATTR, fd, name, value, size) as ssize_t
+}
+
+// fs/xattr.c
+#[inline(always)]
+pub unsafe fn fsetxattr(fd: c_int,
+                        name: *const c_char,
+                        value: *const c_char,
+                        size: size_t,
+                        flags: c_int)
+                        -> ssize_t {
+    syscall!(FSETXATTR, fd, name, value, size, flags) as ssize_t
+}
+
+// fs/xattr.c
+#[inline(always)]
+pub unsafe fn flistxattr(fd: c_int,
+                         list: *mut c_char,
+                         size: size_t)
+                         -> ssize_t {
+    syscall!(FLISTXATTR, fd, list, size) as ssize_t
+}
+
+// fs/xattr.c
+#[inline(always)]
+pub unsafe fn fremovexattr(fd: c_int, name: *const c_char) -> ssize_t {
+    syscall!(FREMOVEXATTR, fd, name) as ssize_t
+}
+
+// fs/notify/inotify/inotify_user.c
+#[inline(always)]
+pub unsafe fn inotify_init1(flags: c_int) -> ssize_t {
+    syscall!(INOTIFY_INIT1, flags) as ssize_t
+}
+
+// fs/notify/inotify/inotify_user.c
+#[inline(always)]
+pub unsafe fn inotify_add_watch(fd: c_int,
+                                pathname: *const c_char,
+                                mask: u32)
+                                -> ssize_t {
+    syscall!(INOTIFY_ADD_WATCH, fd, pathname, mask) as ssize_t
+}
+
+// fs/notify/inotify/inotify_user.c
+#[inline(always)]
+pub unsafe fn inotify_rm_watch(fd: c_int, wd: c_int) -> ssize_t {
+    syscall!(INOTIFY_RM_WATCH, fd, wd) as ssize_t
+}
+
+// fs/eventfd.c
+#[inline(always)]
+pub unsafe fn eventfd2(count: c_uint, flags: c_int) -> ssize_t {
+    syscall!(EVENTFD2, count, flags) as ssize_t
+}
+
+// fs/eventpoll.c
+#[inline(always)]
+pub unsafe fn epoll_create1(flags: c_int) -> ssize_t {
+    syscall!(EPOLL_CREATE1, flags) as ssize_t
+}
+
+// fs/eventpoll.c
+#[inline(always)]
+pub unsafe fn epoll_ctl(epfd: c_int,
+                        op: c_int,
+                        fd: c_int,
+                        event: *mut epoll_event)
+                        -> ssize_t {
+    syscall!(EPOLL_CTL, epfd, op, fd, event) as ssize_t
+}
+
+// fs/eventpoll.c
+//
+// The signal mask is left alone, so `sigmask` is NULL and `sigsetsize`
+// unused.
+#[inline(always)]
+pub unsafe fn epoll_pwait(epfd: c_int,
+                          events: *mut epoll_event,
+                          maxevents: c_int,
+                          timeout: c_int)
+                          -> ssize_t {
+    syscall!(EPOLL_PWAIT, epfd, events, maxevents, timeout, 0, 0) as ssize_t
+}
+
+// fs/timerfd.c
+#[inline(always)]
+pub unsafe fn timerfd_create(clockid: clockid_t, flags: c_int) -> ssize_t {
+    syscall!(TIMERFD_CREATE, clockid, flags) as ssize_t
+}
+
+// fs/timerfd.c
+#[inline(always)]
+pub unsafe fn timerfd_settime(ufd: c_int,
+                              flags: c_int,
+                              utmr: *const itimerspec,
+                              otmr: *mut itimerspec)
+                              -> ssize_t {
+    syscall!(TIMERFD_SETTIME, ufd, flags, utmr, otmr) as ssize_t
+}
+
+// fs/timerfd.c
+#[inline(always)]
+pub unsafe fn timerfd_gettime(ufd: c_int, otmr: *mut itimerspec) -> ssize_t {
+    syscall!(TIMERFD_GETTIME, ufd, otmr) as ssize_t
+}
+
+// fs/io_uring.c
+#[inline(always)]
+pub unsafe fn io_uring_setup(entries: u32, p: *mut io_uring_params)
+                             -> ssize_t {
+    syscall!(IO_URING_SETUP, entries, p) as ssize_t
+}
+
+// fs/io_uring.c
+//
+// Like `epoll_pwait`, the signal mask is left alone.
+#[inline(always)]
+pub unsafe fn io_uring_enter(fd: c_int,
+                             to_submit: u32,
+                             min_complete: u32,
+                             flags: c_uint)
+                             -> ssize_t {
+    syscall!(IO_URING_ENTER, fd, to_submit, min_complete, flags, 0, 0)
+        as ssize_t
+}
+
+// mm/memfd.c
+#[inline(always)]
+pub unsafe fn memfd_create(name: *const c_char, flags: c_uint) -> ssize_t {
+    syscall!(MEMFD_CREATE, name, flags) as ssize_t
+}
+
+// mm/mmap.c
+//
+// The result is an address, so errors have to be told apart with
+// `IS_ERR_VALUE` (i.e. `-4095 <= ret < 0`) rather than `ret < 0`
+#[inline(always)]
+pub unsafe fn mmap(addr: usize,
+                   len: size_t,
+                   prot: c_int,
+                   flags: c_int,
+                   fd: c_int,
+                   offset: loff_t)
+                   -> ssize_t {
+    // NOTE `offset` is in units of 4096 bytes regardless of the page size
+    #[cfg(target_pointer_width = "32")]
+    #[inline(always)]
+    unsafe fn mmap(addr: usize,
+                   len: size_t,
+                   prot: c_int,
+                   flags: c_int,
+                   fd: c_int,
+                   offset: loff_t)
+                   -> ssize_t {
+        syscall!(MMAP2, addr, len, prot, flags, fd, (offset >> 12) as usize)
+            as ssize_t
+    }
+    #[cfg(target_pointer_width = "64")]
+    #[inline(always)]
+    unsafe fn mmap(addr: usize,
+                   len: size_t,
+                   prot: c_int,
+                   flags: c_int,
+                   fd: c_int,
+                   offset: loff_t)
+                   -> ssize_t {
+        syscall!(MMAP, addr, len, prot, flags, fd, offset) as ssize_t
+    }
+    mmap(addr, len, prot, flags, fd, offset)
+}
+
+// mm/mmap.c
+#[inline(always)]
+pub unsafe fn munmap(addr: usize, len: size_t) -> ssize_t {
+    syscall!(MUNMAP, addr, len) as ssize_t
+}
+
+// mm/msync.c
+#[inline(always)]
+pub unsafe fn msync(start: usize, len: size_t, flags: c_int) -> ssize_t {
+    syscall!(MSYNC, start, len, flags) as ssize_t
+}
+
+// mm/madvise.c
+#[inline(always)]
+pub unsafe fn madvise(start: usize, len: size_t, behavior: c_int) -> ssize_t {
+    syscall!(MADVISE, start, len, behavior) as ssize_t
+}
+
+// mm/mlock.c
+#[inline(always)]
+pub unsafe fn mlock(start: usize, len: size_t) -> ssize_t {
+    syscall!(MLOCK, start, len) as ssize_t
+}
+
+// mm/mlock.c
+#[inline(always)]
+pub unsafe fn munlock(start: usize, len: size_t) -> ssize_t {
+    syscall!(MUNLOCK, start, len) as ssize_t
+}
+
 // fs/ioctl.c
 #[inline(always)]
 pub unsafe fn ioctl(fd: c_int, cmd: c_uint, arg: c_ulong) -> ssize_t {
@@ -346,6 +1180,40 @@ pub unsafe fn lstat64(filename: *const c_char, statbuf: *mut stat64) -> ssize_t
     lstat64(filename, statbuf)
 }
 
+// fs/stat.c
+#[inline(always)]
+pub unsafe fn fstatat64(dfd: c_int,
+                        filename: *const c_char,
+                        statbuf: *mut stat64,
+                        flag: c_int)
+                        -> ssize_t {
+    #[cfg(target_pointer_width = "32")]
+    #[inline(always)]
+    unsafe fn fstatat64(dfd: c_int,
+                        filename: *const c_char,
+                        statbuf: *mut stat64,
+                        flag: c_int)
+                        -> ssize_t {
+        syscall!(FSTATAT64, dfd, filename, statbuf, flag) as ssize_t
+    }
+    #[cfg(target_pointer_width = "64")]
+    #[inline(always)]
+    unsafe fn fstatat64(dfd: c_int,
+                        filename: *const c_char,
+                        statbuf: *mut stat64,
+                        flag: c_int)
+                        -> ssize_t {
+        syscall!(NEWFSTATAT, dfd, filename, statbuf, flag) as ssize_t
+    }
+    fstatat64(dfd, filename, statbuf, flag)
+}
+
+// fs/dcache.c
+#[inline(always)]
+pub unsafe fn getcwd(buf: *mut c_char, size: size_t) -> ssize_t {
+    syscall!(GETCWD, buf, size) as ssize_t
+}
+
 // fs/stat.c
 #[inline(always)]
 pub unsafe fn readlink(path: *const c_char, buf: *mut c_char, bufsiz: c_int)
@@ -357,7 +1225,25 @@ pub unsafe fn readlink(path: *const c_char, buf: *mut c_char, bufsiz: c_int)
 // fs/fcntl.c
 #[inline(always)]
 pub unsafe fn fcntl(fd: c_int, cmd: c_uint, arg: c_ulong) -> ssize_t {
-    syscall!(FCNTL, fd, cmd, arg) as ssize_t
+    // On 32-bit architectures only `fcntl64` takes a `flock64` (with 64-bit
+    // offsets) for the locking commands. It behaves like `fcntl` otherwise.
+    #[cfg(target_pointer_width = "32")]
+    #[inline(always)]
+    unsafe fn fcntl(fd: c_int, cmd: c_uint, arg: c_ulong) -> ssize_t {
+        syscall!(FCNTL64, fd, cmd, arg) as ssize_t
+    }
+    #[cfg(target_pointer_width = "64")]
+    #[inline(always)]
+    unsafe fn fcntl(fd: c_int, cmd: c_uint, arg: c_ulong) -> ssize_t {
+        syscall!(FCNTL, fd, cmd, arg) as ssize_t
+    }
+    fcntl(fd, cmd, arg)
+}
+
+// fs/locks.c
+#[inline(always)]
+pub unsafe fn flock(fd: c_uint, cmd: c_uint) -> ssize_t {
+    syscall!(FLOCK, fd, cmd) as ssize_t
 }
 
 // fs/namei.c
@@ -366,6 +1252,38 @@ pub unsafe fn rename(oldname: *const c_char, newname: *const c_char) -> ssize_t
     syscall!(RENAMEAT, AT_FDCWD, oldname, AT_FDCWD, newname) as ssize_t
 }
 
+// fs/namei.c
+#[inline(always)]
+pub unsafe fn linkat(olddfd: c_int,
+                     oldname: *const c_char,
+                     newdfd: c_int,
+                     newname: *const c_char,
+                     flags: c_int)
+                     -> ssize_t {
+    syscall!(LINKAT, olddfd, oldname, newdfd, newname, flags) as ssize_t
+}
+
+// fs/namei.c
+#[inline(always)]
+pub unsafe fn renameat(olddfd: c_int,
+                       oldname: *const c_char,
+                       newdfd: c_int,
+                       newname: *const c_char)
+                       -> ssize_t {
+    syscall!(RENAMEAT, olddfd, oldname, newdfd, newname) as ssize_t
+}
+
+// fs/namei.c
+#[inline(always)]
+pub unsafe fn renameat2(olddfd: c_int,
+                        oldname: *const c_char,
+                        newdfd: c_int,
+                        newname: *const c_char,
+                        flags: c_uint)
+                        -> ssize_t {
+    syscall!(RENAMEAT2, olddfd, oldname, newdfd, newname, flags) as ssize_t
+}
+
 // fs/namei.c
 #[inline(always)]
 pub unsafe fn unlink(pathname: *const c_char) -> ssize_t {
@@ -378,6 +1296,15 @@ pub unsafe fn rmdir(pathname: *const c_char) -> ssize_t {
     syscall!(UNLINKAT, AT_FDCWD, pathname, AT_REMOVEDIR) as ssize_t
 }
 
+// fs/namei.c
+#[inline(always)]
+pub unsafe fn unlinkat(dfd: c_int,
+                       pathname: *const c_char,
+                       flag: c_int)
+                       -> ssize_t {
+    syscall!(UNLINKAT, dfd, pathname, flag) as ssize_t
+}
+
 // fs/namei.c
 #[inline(always)]
 pub unsafe fn link(oldname: *const c_char, newname: *const c_char) -> ssize_t {
@@ -390,12 +1317,30 @@ pub unsafe fn symlink(oldname: *const c_char, newname: *const c_char) -> ssize_t
     syscall!(SYMLINKAT, oldname, AT_FDCWD, newname) as ssize_t
 }
 
+// fs/namei.c
+#[inline(always)]
+pub unsafe fn symlinkat(oldname: *const c_char,
+                        newdfd: c_int,
+                        newname: *const c_char)
+                        -> ssize_t {
+    syscall!(SYMLINKAT, oldname, newdfd, newname) as ssize_t
+}
+
 // fs/namei.c
 #[inline(always)]
 pub unsafe fn mkdir(pathname: *const c_char, mode: umode_t) -> ssize_t {
     syscall!(MKDIRAT, AT_FDCWD, pathname, mode) as ssize_t
 }
 
+// fs/namei.c
+#[inline(always)]
+pub unsafe fn mkdirat(dfd: c_int,
+                      pathname: *const c_char,
+                      mode: umode_t)
+                      -> ssize_t {
+    syscall!(MKDIRAT, dfd, pathname, mode) as ssize_t
+}
+
 // fs/readdir.c
 pub unsafe fn getdents64(fd: c_int, dirent: *mut linux_dirent64, count: c_uint)
     -> ssize_t
@@ -10,5 +10,70 @@ pub const O_CLOEXEC: c_int = 0x400000;
 pub const O_CREAT: c_int = 0x0200;
 pub const O_DIRECTORY: c_int = 0o00200000;
 pub const O_EXCL: c_int = 0x0800;
+pub const O_NOCTTY: c_int = 0x8000;
+pub const O_NOFOLLOW: c_int = 0x20000;
+pub const O_NONBLOCK: c_int = 0x4004;
 pub const O_PATH: c_int = 0x1000000;
+pub const O_TMPFILE: c_int = 0x2000000 | O_DIRECTORY;
 pub const O_TRUNC: c_int = 0x0400;
+
+pub const FICLONE: c_uint = 0x80049409;
+pub const FIONBIO: c_uint = 0x8004667e;
+pub const TCGETS: c_uint = 0x40245408;
+pub const TCSETS: c_uint = 0x80245409;
+pub const TCSETSW: c_uint = 0x8024540a;
+pub const TCSETSF: c_uint = 0x8024540b;
+pub const TIOCGWINSZ: c_uint = 0x40087468;
+pub const TIOCSWINSZ: c_uint = 0x80087467;
+pub const TIOCGPTN: c_uint = 0x40047486;
+pub const TIOCSPTLCK: c_uint = 0x80047487;
+pub const TIOCGPTPEER: c_uint = 0x20007489;
+
+pub const MAP_ANONYMOUS: c_int = 0x20;
+
+// arch/sparc/include/uapi/asm/resource.h
+pub const RLIMIT_RSS: c_uint = 5;
+pub const RLIMIT_NOFILE: c_uint = 6;
+pub const RLIMIT_NPROC: c_uint = 7;
+pub const RLIMIT_MEMLOCK: c_uint = 8;
+pub const RLIMIT_AS: c_uint = 9;
+
+// include/uapi/linux/audit.h
+pub const AUDIT_ARCH_SPARC64: u32 = 0x8000002b;
+
+// arch/sparc/include/asm/seccomp.h
+pub const SECCOMP_ARCH_NATIVE: u32 = AUDIT_ARCH_SPARC64;
+
+// arch/sparc/include/uapi/asm/termbits.h
+pub const IGNBRK: tcflag_t = 0o000001;
+pub const BRKINT: tcflag_t = 0o000002;
+pub const PARMRK: tcflag_t = 0o000010;
+pub const ISTRIP: tcflag_t = 0o000040;
+pub const INLCR: tcflag_t = 0o000100;
+pub const IGNCR: tcflag_t = 0o000200;
+pub const ICRNL: tcflag_t = 0o000400;
+pub const IXON: tcflag_t = 0o002000;
+pub const OPOST: tcflag_t = 0o000001;
+pub const CSIZE: tcflag_t = 0o000060;
+pub const CS8: tcflag_t = 0o000060;
+pub const PARENB: tcflag_t = 0o000400;
+pub const ISIG: tcflag_t = 0o000001;
+pub const ICANON: tcflag_t = 0o000002;
+pub const ECHO: tcflag_t = 0o000010;
+pub const ECHONL: tcflag_t = 0o000100;
+pub const IEXTEN: tcflag_t = 0o100000;
+pub const VMIN: usize = 4;
+pub const VTIME: usize = 5;
+pub const NCCS: usize = 17;
+
+// arch/sparc/include/uapi/asm/termbits.h
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct termios {
+    pub c_iflag: tcflag_t,
+    pub c_oflag: tcflag_t,
+    pub c_cflag: tcflag_t,
+    pub c_lflag: tcflag_t,
+    pub c_line: cc_t,
+    pub c_cc: [cc_t; NCCS],
+}
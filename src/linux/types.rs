@@ -10,6 +10,8 @@ pub type clockid_t = __kernel_clockid_t;
 pub type loff_t = __kernel_loff_t;
 pub type mode_t = __kernel_mode_t;
 pub type nlink_t = u32;
+pub type pid_t = __kernel_pid_t;
+pub type suseconds_t = __kernel_suseconds_t;
 pub type time_t = __kernel_time_t;
 pub type umode_t = c_ushort;
 
@@ -21,9 +23,27 @@ type __kernel_loff_t = c_longlong;
 type __kernel_long_t = c_long;
 type __kernel_mode_t = c_uint;
 type __kernel_off64_t = c_longlong;
+type __kernel_pid_t = c_int;
+#[cfg(not(target_arch = "sparc64"))] type __kernel_suseconds_t = c_long;
+#[cfg(target_arch = "sparc64")] type __kernel_suseconds_t = c_int;
 type __kernel_time_t = __kernel_long_t;
 type __kernel_uid_t = c_uint;
 
+// include/uapi/asm-generic/termbits.h
+pub type cc_t = c_uchar;
+pub type speed_t = c_uint;
+pub type tcflag_t = c_uint;
+
+// include/uapi/asm-generic/termios.h
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct winsize {
+    pub ws_row: c_ushort,
+    pub ws_col: c_ushort,
+    pub ws_xpixel: c_ushort,
+    pub ws_ypixel: c_ushort,
+}
+
 // include/uapi/linux/time.h
 #[derive(Clone, Copy)]
 #[repr(C)]
@@ -32,6 +52,33 @@ pub struct timespec {
     pub tv_nsec: c_long,
 }
 
+// include/uapi/linux/time.h
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct timeval {
+    pub tv_sec: time_t,
+    pub tv_usec: suseconds_t,
+}
+
+// include/uapi/linux/time.h
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct itimerspec {
+    pub it_interval: timespec,
+    pub it_value: timespec,
+}
+
+// include/uapi/asm-generic/fcntl.h
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct flock64 {
+    pub l_type: c_short,
+    pub l_whence: c_short,
+    pub l_start: loff_t,
+    pub l_len: loff_t,
+    pub l_pid: pid_t,
+}
+
 // include/linux/dirent.h
 #[derive(Clone, Copy)]
 #[repr(C)]
@@ -43,6 +90,217 @@ pub struct linux_dirent64 {
     pub d_name: [c_char; 0],
 }
 
+// include/uapi/linux/inotify.h
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct inotify_event {
+    pub wd: c_int,
+    pub mask: u32,
+    pub cookie: u32,
+    pub len: u32,
+    // Followed by `len` bytes of NUL padded name
+}
+
+// include/uapi/linux/eventpoll.h
+//
+// Packed on x86_64 only, to match the layout of 32-bit x86.
+#[derive(Clone, Copy)]
+#[cfg_attr(target_arch = "x86_64", repr(C, packed))]
+#[cfg_attr(not(target_arch = "x86_64"), repr(C))]
+pub struct epoll_event {
+    pub events: u32,
+    pub data: u64,
+}
+
+// include/uapi/asm-generic/poll.h
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct pollfd {
+    pub fd: c_int,
+    pub events: c_short,
+    pub revents: c_short,
+}
+
+// include/uapi/linux/uio.h
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct iovec {
+    pub iov_base: *mut c_char,
+    pub iov_len: size_t,
+}
+
+// include/uapi/linux/stat.h
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct statx_timestamp {
+    pub tv_sec: i64,
+    pub tv_nsec: u32,
+    __reserved: i32,
+}
+
+// include/uapi/linux/stat.h
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct statx {
+    pub stx_mask: u32,
+    pub stx_blksize: u32,
+    pub stx_attributes: u64,
+    pub stx_nlink: u32,
+    pub stx_uid: u32,
+    pub stx_gid: u32,
+    pub stx_mode: u16,
+    __spare0: u16,
+    pub stx_ino: u64,
+    pub stx_size: u64,
+    pub stx_blocks: u64,
+    pub stx_attributes_mask: u64,
+    pub stx_atime: statx_timestamp,
+    pub stx_btime: statx_timestamp,
+    pub stx_ctime: statx_timestamp,
+    pub stx_mtime: statx_timestamp,
+    pub stx_rdev_major: u32,
+    pub stx_rdev_minor: u32,
+    pub stx_dev_major: u32,
+    pub stx_dev_minor: u32,
+    __spare2: [u64; 14],
+}
+
+// include/uapi/linux/io_uring.h
+//
+// Only the fields used by the opcodes we submit are named; the unions of
+// the C struct are flattened to their first member.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct io_uring_sqe {
+    pub opcode: u8,
+    pub flags: u8,
+    pub ioprio: u16,
+    pub fd: i32,
+    // `off` or `addr2`
+    pub off: u64,
+    pub addr: u64,
+    pub len: u32,
+    // `rw_flags`, `fsync_flags`, `open_flags`, `statx_flags`, ...
+    pub op_flags: u32,
+    pub user_data: u64,
+    pub buf_index: u16,
+    pub personality: u16,
+    pub splice_fd_in: i32,
+    __pad2: [u64; 2],
+}
+
+// include/uapi/linux/io_uring.h
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct io_uring_cqe {
+    pub user_data: u64,
+    pub res: i32,
+    pub flags: u32,
+}
+
+// include/uapi/linux/io_uring.h
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct io_sqring_offsets {
+    pub head: u32,
+    pub tail: u32,
+    pub ring_mask: u32,
+    pub ring_entries: u32,
+    pub flags: u32,
+    pub dropped: u32,
+    pub array: u32,
+    resv1: u32,
+    resv2: u64,
+}
+
+// include/uapi/linux/io_uring.h
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct io_cqring_offsets {
+    pub head: u32,
+    pub tail: u32,
+    pub ring_mask: u32,
+    pub ring_entries: u32,
+    pub overflow: u32,
+    pub cqes: u32,
+    pub flags: u32,
+    resv1: u32,
+    resv2: u64,
+}
+
+// include/uapi/linux/io_uring.h
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct io_uring_params {
+    pub sq_entries: u32,
+    pub cq_entries: u32,
+    pub flags: u32,
+    pub sq_thread_cpu: u32,
+    pub sq_thread_idle: u32,
+    pub features: u32,
+    pub wq_fd: u32,
+    resv: [u32; 3],
+    pub sq_off: io_sqring_offsets,
+    pub cq_off: io_cqring_offsets,
+}
+
+// include/uapi/linux/resource.h
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct rlimit64 {
+    pub rlim_cur: u64,
+    pub rlim_max: u64,
+}
+
+// include/uapi/linux/resource.h
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct rusage {
+    pub ru_utime: timeval,
+    pub ru_stime: timeval,
+    pub ru_maxrss: c_long,
+    pub ru_ixrss: c_long,
+    pub ru_idrss: c_long,
+    pub ru_isrss: c_long,
+    pub ru_minflt: c_long,
+    pub ru_majflt: c_long,
+    pub ru_nswap: c_long,
+    pub ru_inblock: c_long,
+    pub ru_oublock: c_long,
+    pub ru_msgsnd: c_long,
+    pub ru_msgrcv: c_long,
+    pub ru_nsignals: c_long,
+    pub ru_nvcsw: c_long,
+    pub ru_nivcsw: c_long,
+}
+
+// include/uapi/linux/filter.h
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct sock_filter {
+    pub code: u16,
+    pub jt: u8,
+    pub jf: u8,
+    pub k: u32,
+}
+
+// include/uapi/linux/filter.h
+#[repr(C)]
+pub struct sock_fprog {
+    pub len: c_ushort,
+    pub filter: *const sock_filter,
+}
+
+// include/uapi/linux/seccomp.h
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct seccomp_data {
+    pub nr: c_int,
+    pub arch: u32,
+    pub instruction_pointer: u64,
+    pub args: [u64; 6],
+}
+
 // Where from?
 pub type blkcnt64_t = i64;
 #[cfg(not(any(target_arch = "mips", target_arch = "mips64")))] pub type dev_t = u64;
@@ -0,0 +1,254 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Waiting for readiness on many file descriptors at once.
+//!
+//! An `Epoll` instance holds a set of file descriptors, each registered with
+//! an interest mask, a bitwise OR of the `EPOLL*` constants, and a token.
+//! `wait` blocks until some of them are ready and reports which, by token.
+//!
+//! By default readiness is level-triggered: a descriptor is reported on
+//! every `wait` for as long as it is ready. With `EPOLLET` it's only
+//! reported when it becomes ready, so it has to be drained until it fails
+//! with `ErrorKind::WouldBlock` before waiting again. Edge-triggered
+//! descriptors should therefore be in nonblocking mode. With `EPOLLONESHOT`
+//! a descriptor is disabled after it has been reported once, until it is
+//! re-armed with `modify`.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! #![feature(epoll)]
+//! use std::io;
+//! use std::os::linux::epoll::{Epoll, Events, EPOLLIN};
+//!
+//! # fn foo() -> io::Result<()> {
+//! let epoll = try!(Epoll::new());
+//! try!(epoll.add(&io::stdin(), EPOLLIN, 0));
+//!
+//! let mut events = Events::with_capacity(16);
+//! loop {
+//!     try!(epoll.wait(&mut events, None));
+//!     for event in &events {
+//!         if event.token == 0 {
+//!             println!("stdin is readable");
+//!         }
+//!     }
+//! }
+//! # }
+//! ```
+
+#![unstable(feature = "epoll", issue = "0")]
+
+use libc;
+
+use fmt;
+use io;
+use os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use slice;
+use sys::epoll as imp;
+use sys_common::FromInner;
+use time::Duration;
+
+/// Interest and readiness: there is data to read.
+pub const EPOLLIN: u32 = libc::EPOLLIN;
+/// Interest and readiness: there is urgent data to read, e.g. out-of-band
+/// data on a TCP socket.
+pub const EPOLLPRI: u32 = libc::EPOLLPRI;
+/// Interest and readiness: writing won't block.
+pub const EPOLLOUT: u32 = libc::EPOLLOUT;
+/// Interest and readiness: the peer of a stream socket shut down its
+/// writing half.
+pub const EPOLLRDHUP: u32 = libc::EPOLLRDHUP;
+/// Readiness: an error is pending on the descriptor. Always reported, no
+/// need to ask for it.
+pub const EPOLLERR: u32 = libc::EPOLLERR;
+/// Readiness: the descriptor was hung up, e.g. the write end of a pipe was
+/// closed. Always reported, no need to ask for it.
+pub const EPOLLHUP: u32 = libc::EPOLLHUP;
+
+/// Option: report readiness edge-triggered instead of level-triggered.
+pub const EPOLLET: u32 = libc::EPOLLET;
+/// Option: disable the descriptor after it has been reported once. It is
+/// re-armed with `Epoll::modify`.
+pub const EPOLLONESHOT: u32 = libc::EPOLLONESHOT;
+/// Option: when the same descriptor is registered with several `Epoll`
+/// instances using this option, wake only one of them instead of all.
+/// Only valid with `add`.
+pub const EPOLLEXCLUSIVE: u32 = libc::EPOLLEXCLUSIVE;
+/// Option: keep the system from suspending while an event is pending or
+/// being handled. Needs `CAP_BLOCK_SUSPEND`.
+pub const EPOLLWAKEUP: u32 = libc::EPOLLWAKEUP;
+
+/// An epoll instance.
+pub struct Epoll(imp::Epoll);
+
+impl Epoll {
+    /// Creates an instance with no registered descriptors.
+    pub fn new() -> io::Result<Epoll> {
+        imp::Epoll::new().map(Epoll)
+    }
+
+    /// Registers `fd` with the interest mask `events`. `token` is reported
+    /// back with each of its events.
+    ///
+    /// Fails with `ErrorKind::AlreadyExists` if `fd` is already registered.
+    pub fn add<F: AsRawFd + ?Sized>(&self, fd: &F, events: u32, token: u64)
+                                    -> io::Result<()> {
+        self.0.ctl(libc::EPOLL_CTL_ADD, fd.as_raw_fd(), events, token)
+    }
+
+    /// Changes the interest mask and token of the registered `fd`. This also
+    /// re-arms it if it was registered with `EPOLLONESHOT`.
+    pub fn modify<F: AsRawFd + ?Sized>(&self, fd: &F, events: u32, token: u64)
+                                       -> io::Result<()> {
+        self.0.ctl(libc::EPOLL_CTL_MOD, fd.as_raw_fd(), events, token)
+    }
+
+    /// Unregisters `fd`.
+    ///
+    /// Closing a file descriptor unregisters it automatically, unless it
+    /// was duplicated and other copies are still open.
+    pub fn delete<F: AsRawFd + ?Sized>(&self, fd: &F) -> io::Result<()> {
+        self.0.ctl(libc::EPOLL_CTL_DEL, fd.as_raw_fd(), 0, 0)
+    }
+
+    /// Waits until at least one registered descriptor is ready or `timeout`
+    /// has passed, and replaces the contents of `events` with what's ready.
+    ///
+    /// At most `events.capacity()` events are returned at a time; any others
+    /// are kept for the next call. A timeout of `None` waits forever, other
+    /// timeouts are rounded up to whole milliseconds.
+    ///
+    /// Returns the number of events, 0 if the timeout expired. Fails with
+    /// `ErrorKind::Interrupted` if a signal handler ran while waiting.
+    pub fn wait(&self, events: &mut Events, timeout: Option<Duration>)
+                -> io::Result<usize> {
+        self.0.wait(&mut events.list, timeout)
+    }
+}
+
+impl fmt::Debug for Epoll {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Epoll").field("fd", &self.as_raw_fd()).finish()
+    }
+}
+
+impl AsRawFd for Epoll {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.fd().raw()
+    }
+}
+
+impl FromRawFd for Epoll {
+    unsafe fn from_raw_fd(fd: RawFd) -> Epoll {
+        Epoll(imp::Epoll::from_inner(fd))
+    }
+}
+
+impl IntoRawFd for Epoll {
+    fn into_raw_fd(self) -> RawFd {
+        self.0.into_fd().into_raw()
+    }
+}
+
+/// A buffer for the events returned by `Epoll::wait`.
+pub struct Events {
+    list: Vec<libc::epoll_event>,
+}
+
+impl Events {
+    /// Creates a buffer that holds up to `capacity` events.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn with_capacity(capacity: usize) -> Events {
+        assert!(capacity > 0, "an epoll event buffer needs a capacity");
+        Events { list: Vec::with_capacity(capacity) }
+    }
+
+    /// Returns the number of events the buffer holds.
+    pub fn capacity(&self) -> usize {
+        self.list.capacity()
+    }
+
+    /// Returns the number of events returned by the last `wait`.
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Returns whether the last `wait` timed out.
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Returns an iterator over the events returned by the last `wait`.
+    pub fn iter(&self) -> Iter {
+        Iter { inner: self.list.iter() }
+    }
+
+    /// Removes all events from the buffer.
+    pub fn clear(&mut self) {
+        self.list.clear()
+    }
+}
+
+impl fmt::Debug for Events {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a> IntoIterator for &'a Events {
+    type Item = Event;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+/// An event returned by `Epoll::wait`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Event {
+    /// What the descriptor is ready for, a bitwise OR of `EPOLLIN`,
+    /// `EPOLLPRI`, `EPOLLOUT`, `EPOLLRDHUP`, `EPOLLERR` and `EPOLLHUP`.
+    pub events: u32,
+    /// The token the descriptor was registered with.
+    pub token: u64,
+}
+
+/// An iterator over the events in an `Events` buffer.
+pub struct Iter<'a> {
+    inner: slice::Iter<'a, libc::epoll_event>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Event> {
+        // Copy the fields out first, `epoll_event` is packed on x86_64
+        self.inner.next().map(|&event| {
+            let libc::epoll_event { events, data } = event;
+            Event { events: events, token: data }
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a> fmt::Debug for Iter<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad("Iter { .. }")
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Event counters that can be waited on like any file descriptor.
+//!
+//! An `EventFd` holds a 64-bit counter. Writing adds to it and reading
+//! waits until it's nonzero, then takes from it. It is readable for `poll`
+//! and `epoll` while the counter is nonzero, which makes it a cheap way to
+//! wake up an event loop from another thread.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! #![feature(eventfd)]
+//! use std::os::linux::eventfd::EventFd;
+//!
+//! # fn foo() -> std::io::Result<()> {
+//! let wakeup = try!(EventFd::new(0));
+//! try!(wakeup.write(1));
+//! try!(wakeup.write(2));
+//! assert_eq!(try!(wakeup.read()), 3);
+//! # Ok(())
+//! # }
+//! ```
+
+#![unstable(feature = "eventfd", issue = "0")]
+
+use fmt;
+use io;
+use os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use sys::eventfd as imp;
+use sys_common::FromInner;
+
+/// An eventfd counter.
+pub struct EventFd(imp::EventFd);
+
+impl EventFd {
+    /// Creates a counter starting at `initval`, in counter mode: `read`
+    /// returns the whole counter and resets it to 0.
+    pub fn new(initval: u32) -> io::Result<EventFd> {
+        imp::EventFd::new(initval, false).map(EventFd)
+    }
+
+    /// Creates a counter starting at `initval`, in semaphore mode: `read`
+    /// returns 1 and decrements the counter by 1.
+    pub fn semaphore(initval: u32) -> io::Result<EventFd> {
+        imp::EventFd::new(initval, true).map(EventFd)
+    }
+
+    /// Waits until the counter is nonzero, then takes from it as described
+    /// for `new` and `semaphore`.
+    ///
+    /// In nonblocking mode, fails with `ErrorKind::WouldBlock` instead of
+    /// waiting.
+    pub fn read(&self) -> io::Result<u64> {
+        self.0.read()
+    }
+
+    /// Adds `value` to the counter, waiting if that would take it past
+    /// `u64::max_value() - 1`.
+    ///
+    /// In nonblocking mode, fails with `ErrorKind::WouldBlock` instead of
+    /// waiting. Fails with `EINVAL` if `value` is `u64::max_value()`.
+    pub fn write(&self, value: u64) -> io::Result<()> {
+        self.0.write(value)
+    }
+
+    /// Moves the counter into or out of nonblocking mode.
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.0.set_nonblocking(nonblocking)
+    }
+}
+
+impl fmt::Debug for EventFd {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EventFd").field("fd", &self.as_raw_fd()).finish()
+    }
+}
+
+impl AsRawFd for EventFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.fd().raw()
+    }
+}
+
+impl FromRawFd for EventFd {
+    unsafe fn from_raw_fd(fd: RawFd) -> EventFd {
+        EventFd(imp::EventFd::from_inner(fd))
+    }
+}
+
+impl IntoRawFd for EventFd {
+    fn into_raw_fd(self) -> RawFd {
+        self.0.into_fd().into_raw()
+    }
+}
@@ -0,0 +1,548 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![stable(feature = "metadata_ext", since = "1.1.0")]
+
+use libc;
+
+use ffi::OsStr;
+use fs::{File, Metadata, OpenOptions, ReadDir};
+use io;
+use ops::Range;
+use os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use path::{Path, PathBuf};
+use sys;
+use sys_common::{AsInner, FromInner};
+
+#[allow(deprecated)]
+use os::linux::raw;
+
+pub mod xattr;
+
+/// OS-specific extension methods for `fs::Metadata`
+#[stable(feature = "metadata_ext", since = "1.1.0")]
+pub trait MetadataExt {
+    /// Gain a reference to the underlying `stat` structure which contains
+    /// the raw information returned by the OS.
+    ///
+    /// The contents of the returned `stat` are **not** consistent across
+    /// Unix platforms. The `os::unix::fs::MetadataExt` trait contains the
+    /// cross-Unix abstractions contained within the raw stat.
+    #[stable(feature = "metadata_ext", since = "1.1.0")]
+    #[rustc_deprecated(since = "1.8.0",
+                       reason = "deprecated in favor of the accessor \
+                                 methods of this trait")]
+    #[allow(deprecated)]
+    fn as_raw_stat(&self) -> &raw::stat;
+
+    #[stable(feature = "metadata_ext2", since = "1.8.0")]
+    fn st_dev(&self) -> u64;
+    #[stable(feature = "metadata_ext2", since = "1.8.0")]
+    fn st_ino(&self) -> u64;
+    #[stable(feature = "metadata_ext2", since = "1.8.0")]
+    fn st_mode(&self) -> u32;
+    #[stable(feature = "metadata_ext2", since = "1.8.0")]
+    fn st_nlink(&self) -> u64;
+    #[stable(feature = "metadata_ext2", since = "1.8.0")]
+    fn st_uid(&self) -> u32;
+    #[stable(feature = "metadata_ext2", since = "1.8.0")]
+    fn st_gid(&self) -> u32;
+    #[stable(feature = "metadata_ext2", since = "1.8.0")]
+    fn st_rdev(&self) -> u64;
+    #[stable(feature = "metadata_ext2", since = "1.8.0")]
+    fn st_size(&self) -> u64;
+    #[stable(feature = "metadata_ext2", since = "1.8.0")]
+    fn st_atime(&self) -> i64;
+    #[stable(feature = "metadata_ext2", since = "1.8.0")]
+    fn st_atime_nsec(&self) -> i64;
+    #[stable(feature = "metadata_ext2", since = "1.8.0")]
+    fn st_mtime(&self) -> i64;
+    #[stable(feature = "metadata_ext2", since = "1.8.0")]
+    fn st_mtime_nsec(&self) -> i64;
+    #[stable(feature = "metadata_ext2", since = "1.8.0")]
+    fn st_ctime(&self) -> i64;
+    #[stable(feature = "metadata_ext2", since = "1.8.0")]
+    fn st_ctime_nsec(&self) -> i64;
+    #[stable(feature = "metadata_ext2", since = "1.8.0")]
+    fn st_blksize(&self) -> u64;
+    #[stable(feature = "metadata_ext2", since = "1.8.0")]
+    fn st_blocks(&self) -> u64;
+}
+
+#[stable(feature = "metadata_ext", since = "1.1.0")]
+impl MetadataExt for Metadata {
+    #[allow(deprecated)]
+    fn as_raw_stat(&self) -> &raw::stat {
+        unsafe {
+            &*(self.as_inner().as_inner() as *const libc::stat64
+                                          as *const raw::stat)
+        }
+    }
+    fn st_dev(&self) -> u64 {
+        self.as_inner().as_inner().st_dev as u64
+    }
+    fn st_ino(&self) -> u64 {
+        self.as_inner().as_inner().st_ino as u64
+    }
+    fn st_mode(&self) -> u32 {
+        self.as_inner().as_inner().st_mode as u32
+    }
+    fn st_nlink(&self) -> u64 {
+        self.as_inner().as_inner().st_nlink as u64
+    }
+    fn st_uid(&self) -> u32 {
+        self.as_inner().as_inner().st_uid as u32
+    }
+    fn st_gid(&self) -> u32 {
+        self.as_inner().as_inner().st_gid as u32
+    }
+    fn st_rdev(&self) -> u64 {
+        self.as_inner().as_inner().st_rdev as u64
+    }
+    fn st_size(&self) -> u64 {
+        self.as_inner().as_inner().st_size as u64
+    }
+    fn st_atime(&self) -> i64 {
+        self.as_inner().as_inner().st_atime as i64
+    }
+    fn st_atime_nsec(&self) -> i64 {
+        self.as_inner().as_inner().st_atime_nsec as i64
+    }
+    fn st_mtime(&self) -> i64 {
+        self.as_inner().as_inner().st_mtime as i64
+    }
+    fn st_mtime_nsec(&self) -> i64 {
+        self.as_inner().as_inner().st_mtime_nsec as i64
+    }
+    fn st_ctime(&self) -> i64 {
+        self.as_inner().as_inner().st_ctime as i64
+    }
+    fn st_ctime_nsec(&self) -> i64 {
+        self.as_inner().as_inner().st_ctime_nsec as i64
+    }
+    fn st_blksize(&self) -> u64 {
+        self.as_inner().as_inner().st_blksize as u64
+    }
+    fn st_blocks(&self) -> u64 {
+        self.as_inner().as_inner().st_blocks as u64
+    }
+}
+
+/// Linux-specific extensions to `fs::File`.
+#[unstable(feature = "linux_file_ext", issue = "0")]
+pub trait FileExt {
+    /// Acquires a shared lock on the byte range of `len` bytes starting at
+    /// `start`, blocking until it can be taken. A `len` of 0 extends the
+    /// range to the end of the file, however large it grows.
+    ///
+    /// These are open file description locks (`F_OFD_SETLKW`): they are
+    /// owned by the open file, not by the process, so they're shared with
+    /// clones of the `File`, they're released when the last of those is
+    /// closed and they conflict with the locks of other open files of the
+    /// same process. Like `flock` locks they're advisory.
+    #[unstable(feature = "linux_file_ext", issue = "0")]
+    fn lock_range_shared(&self, start: u64, len: u64) -> io::Result<()>;
+
+    /// Acquires an exclusive lock on a byte range, blocking until it can be
+    /// taken. See `lock_range_shared` for details.
+    #[unstable(feature = "linux_file_ext", issue = "0")]
+    fn lock_range_exclusive(&self, start: u64, len: u64) -> io::Result<()>;
+
+    /// Tries to acquire a shared lock on a byte range without blocking,
+    /// returning `Ok(false)` if a conflicting lock is held.
+    #[unstable(feature = "linux_file_ext", issue = "0")]
+    fn try_lock_range_shared(&self, start: u64, len: u64) -> io::Result<bool>;
+
+    /// Tries to acquire an exclusive lock on a byte range without blocking,
+    /// returning `Ok(false)` if a conflicting lock is held.
+    #[unstable(feature = "linux_file_ext", issue = "0")]
+    fn try_lock_range_exclusive(&self, start: u64, len: u64)
+                                -> io::Result<bool>;
+
+    /// Releases the locks held on a byte range. Unlocking only part of a
+    /// locked range splits the lock.
+    #[unstable(feature = "linux_file_ext", issue = "0")]
+    fn unlock_range(&self, start: u64, len: u64) -> io::Result<()>;
+
+    /// Allocates disk space for the byte range of `len` bytes starting at
+    /// `offset`, extending the file if the range ends past its end.
+    ///
+    /// Subsequent writes to the range are guaranteed not to fail for lack of
+    /// disk space. Bytes that weren't part of the file before read as zeros.
+    #[unstable(feature = "linux_file_ext", issue = "0")]
+    fn allocate(&self, offset: u64, len: u64) -> io::Result<()>;
+
+    /// Deallocates the disk space of a byte range, turning it into a hole
+    /// that reads as zeros. The size of the file doesn't change.
+    ///
+    /// Fails with `EOPNOTSUPP` on filesystems that don't support holes.
+    #[unstable(feature = "linux_file_ext", issue = "0")]
+    fn punch_hole(&self, offset: u64, len: u64) -> io::Result<()>;
+
+    /// Zeroes a byte range, preferably by converting it to unwritten extents
+    /// instead of writing zeros, and extends the file if the range ends past
+    /// its end.
+    ///
+    /// Fails with `EOPNOTSUPP` on filesystems that don't support it.
+    #[unstable(feature = "linux_file_ext", issue = "0")]
+    fn zero_range(&self, offset: u64, len: u64) -> io::Result<()>;
+
+    /// Returns an iterator over the regions of the file that contain data,
+    /// skipping its holes.
+    ///
+    /// The regions are found with `SEEK_DATA` and `SEEK_HOLE`, so iterating
+    /// moves the cursor of the file. Filesystems without support for holes
+    /// report the whole file as a single region.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(linux_file_ext)]
+    /// use std::fs::File;
+    /// use std::os::linux::fs::FileExt;
+    ///
+    /// # fn foo() -> std::io::Result<()> {
+    /// let f = try!(File::open("disk.img"));
+    /// for region in f.data_regions() {
+    ///     let region = try!(region);
+    ///     println!("data from {} to {}", region.start, region.end);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[unstable(feature = "linux_file_ext", issue = "0")]
+    fn data_regions(&self) -> DataRegions;
+
+    /// Adds seals to the file, a bitwise OR of the `F_SEAL_*` constants.
+    ///
+    /// Only files created by `MemFd` with sealing allowed can be sealed.
+    /// Seals can't be removed, and adding `F_SEAL_WRITE` fails with `EBUSY`
+    /// while the file has writable shared memory maps.
+    #[unstable(feature = "memfd", issue = "0")]
+    fn add_seals(&self, seals: u32) -> io::Result<()>;
+
+    /// Returns the seals of the file, a bitwise OR of the `F_SEAL_*`
+    /// constants.
+    ///
+    /// Fails with `EINVAL` for files that don't support sealing.
+    #[unstable(feature = "memfd", issue = "0")]
+    fn seals(&self) -> io::Result<u32>;
+}
+
+#[unstable(feature = "linux_file_ext", issue = "0")]
+impl FileExt for File {
+    fn lock_range_shared(&self, start: u64, len: u64) -> io::Result<()> {
+        self.as_inner().lock_range(false, start, len, true).map(|_| ())
+    }
+    fn lock_range_exclusive(&self, start: u64, len: u64) -> io::Result<()> {
+        self.as_inner().lock_range(true, start, len, true).map(|_| ())
+    }
+    fn try_lock_range_shared(&self, start: u64, len: u64) -> io::Result<bool> {
+        self.as_inner().lock_range(false, start, len, false)
+    }
+    fn try_lock_range_exclusive(&self, start: u64, len: u64)
+                                -> io::Result<bool> {
+        self.as_inner().lock_range(true, start, len, false)
+    }
+    fn unlock_range(&self, start: u64, len: u64) -> io::Result<()> {
+        self.as_inner().unlock_range(start, len)
+    }
+    fn allocate(&self, offset: u64, len: u64) -> io::Result<()> {
+        self.as_inner().allocate(offset, len)
+    }
+    fn punch_hole(&self, offset: u64, len: u64) -> io::Result<()> {
+        self.as_inner().punch_hole(offset, len)
+    }
+    fn zero_range(&self, offset: u64, len: u64) -> io::Result<()> {
+        self.as_inner().zero_range(offset, len)
+    }
+    fn data_regions(&self) -> DataRegions {
+        DataRegions { file: self, pos: Some(0) }
+    }
+    fn add_seals(&self, seals: u32) -> io::Result<()> {
+        self.as_inner().add_seals(seals)
+    }
+    fn seals(&self) -> io::Result<u32> {
+        self.as_inner().seals()
+    }
+}
+
+/// An iterator over the data regions of a file, as byte ranges.
+///
+/// This struct is created by the `data_regions` method of `FileExt`.
+#[unstable(feature = "linux_file_ext", issue = "0")]
+#[derive(Debug)]
+pub struct DataRegions<'a> {
+    file: &'a File,
+    // `None` once the iterator is exhausted.
+    pos: Option<u64>,
+}
+
+#[unstable(feature = "linux_file_ext", issue = "0")]
+impl<'a> Iterator for DataRegions<'a> {
+    type Item = io::Result<Range<u64>>;
+
+    fn next(&mut self) -> Option<io::Result<Range<u64>>> {
+        let pos = match self.pos.take() {
+            Some(pos) => pos,
+            None => return None,
+        };
+        let file = self.file.as_inner();
+        let start = match file.seek_data(pos) {
+            Ok(Some(start)) => start,
+            Ok(None) => return None,
+            Err(e) => return Some(Err(e)),
+        };
+        match file.seek_hole(start) {
+            Ok(end) => {
+                self.pos = Some(end);
+                Some(Ok(start..end))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// Seal: no more seals can be added.
+#[unstable(feature = "memfd", issue = "0")]
+pub const F_SEAL_SEAL: u32 = libc::F_SEAL_SEAL;
+
+/// Seal: the file can't be made shorter.
+#[unstable(feature = "memfd", issue = "0")]
+pub const F_SEAL_SHRINK: u32 = libc::F_SEAL_SHRINK;
+
+/// Seal: the file can't be made longer.
+#[unstable(feature = "memfd", issue = "0")]
+pub const F_SEAL_GROW: u32 = libc::F_SEAL_GROW;
+
+/// Seal: the contents of the file can't be modified.
+#[unstable(feature = "memfd", issue = "0")]
+pub const F_SEAL_WRITE: u32 = libc::F_SEAL_WRITE;
+
+/// Options for creating anonymous in-memory files with `memfd_create`.
+///
+/// The files live in RAM (or swap) and go away once all their handles are
+/// closed. They are regular files in all other respects: they can be
+/// read, written, resized, mapped and passed to other processes.
+///
+/// Files created with sealing allowed can be locked down with
+/// `FileExt::add_seals`, which lets a process that receives one trust that
+/// its contents won't change under its feet.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(memfd)]
+/// use std::io::Write;
+/// use std::os::linux::fs::{FileExt, MemFd, F_SEAL_SEAL, F_SEAL_SHRINK,
+///                          F_SEAL_GROW, F_SEAL_WRITE};
+///
+/// # fn foo() -> std::io::Result<()> {
+/// let mut f = try!(MemFd::new().allow_sealing(true).create("config"));
+/// try!(f.write_all(b"workers = 4\n"));
+/// try!(f.add_seals(F_SEAL_SHRINK | F_SEAL_GROW | F_SEAL_WRITE | F_SEAL_SEAL));
+/// # Ok(())
+/// # }
+/// ```
+#[unstable(feature = "memfd", issue = "0")]
+#[derive(Clone, Debug)]
+pub struct MemFd {
+    allow_sealing: bool,
+}
+
+impl MemFd {
+    /// Creates a blank set of options, with sealing disallowed.
+    #[unstable(feature = "memfd", issue = "0")]
+    pub fn new() -> MemFd {
+        MemFd { allow_sealing: false }
+    }
+
+    /// Sets whether seals can be added to the file. If not, the file is
+    /// created with `F_SEAL_SEAL` already set.
+    #[unstable(feature = "memfd", issue = "0")]
+    pub fn allow_sealing(&mut self, allow_sealing: bool) -> &mut MemFd {
+        self.allow_sealing = allow_sealing;
+        self
+    }
+
+    /// Creates an empty file, open for reading and writing.
+    ///
+    /// The `name` is only used for debugging, it shows up as the target of
+    /// the `/proc/self/fd` symlink prefixed with `memfd:`. Different files
+    /// can have the same name. It must be at most 249 bytes long.
+    #[unstable(feature = "memfd", issue = "0")]
+    pub fn create<N: AsRef<OsStr>>(&self, name: N) -> io::Result<File> {
+        sys::fs::memfd_create(name.as_ref(), self.allow_sealing)
+            .map(File::from_inner)
+    }
+}
+
+#[unstable(feature = "memfd", issue = "0")]
+impl Default for MemFd {
+    fn default() -> MemFd {
+        MemFd::new()
+    }
+}
+
+/// Flag for `Dir::rename_at`: don't overwrite the destination of the rename,
+/// fail with `ErrorKind::AlreadyExists` instead.
+#[unstable(feature = "dir_fd", issue = "0")]
+pub const RENAME_NOREPLACE: u32 = libc::RENAME_NOREPLACE;
+
+/// Flag for `Dir::rename_at`: atomically exchange the source and the
+/// destination of the rename. Both of them must exist.
+#[unstable(feature = "dir_fd", issue = "0")]
+pub const RENAME_EXCHANGE: u32 = libc::RENAME_EXCHANGE;
+
+/// A handle to an open directory.
+///
+/// All the methods taking a path resolve it relative to this directory,
+/// using the `*at` family of system calls. In contrast to the path based
+/// functions in `std::fs`, they are not affected by concurrent renames of
+/// the directory or of any of its ancestors, which makes it possible to
+/// operate on a directory tree without racing against symlink swaps.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(dir_fd)]
+/// use std::os::linux::fs::Dir;
+///
+/// # fn foo() -> std::io::Result<()> {
+/// let dir = try!(Dir::open("/tmp"));
+/// try!(dir.mkdir_at("foo", 0o755));
+/// let foo = try!(dir.open_dir_at("foo"));
+/// try!(foo.symlink_at("/etc/hostname", "hostname"));
+/// # Ok(())
+/// # }
+/// ```
+#[unstable(feature = "dir_fd", issue = "0")]
+#[derive(Debug)]
+pub struct Dir(sys::fs::Dir);
+
+impl Dir {
+    /// Opens the directory at `path`.
+    #[unstable(feature = "dir_fd", issue = "0")]
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Dir> {
+        sys::fs::Dir::open(path.as_ref()).map(Dir)
+    }
+
+    /// Opens the subdirectory `path` of this directory.
+    ///
+    /// If the last component of `path` is a symbolic link this function fails
+    /// instead of following it.
+    #[unstable(feature = "dir_fd", issue = "0")]
+    pub fn open_dir_at<P: AsRef<Path>>(&self, path: P) -> io::Result<Dir> {
+        self.0.open_dir_at(path.as_ref()).map(Dir)
+    }
+
+    /// Opens the file `path`, relative to this directory, with the options
+    /// specified by `opts`.
+    #[unstable(feature = "dir_fd", issue = "0")]
+    pub fn open_at<P: AsRef<Path>>(&self, path: P, opts: &OpenOptions)
+        -> io::Result<File>
+    {
+        self.0.open_at(path.as_ref(), opts.as_inner()).map(File::from_inner)
+    }
+
+    /// Queries the metadata of `path`, relative to this directory, following
+    /// symbolic links.
+    #[unstable(feature = "dir_fd", issue = "0")]
+    pub fn stat_at<P: AsRef<Path>>(&self, path: P) -> io::Result<Metadata> {
+        self.0.stat_at(path.as_ref()).map(Metadata::from_inner)
+    }
+
+    /// Queries the metadata of `path`, relative to this directory, without
+    /// following symbolic links.
+    #[unstable(feature = "dir_fd", issue = "0")]
+    pub fn lstat_at<P: AsRef<Path>>(&self, path: P) -> io::Result<Metadata> {
+        self.0.lstat_at(path.as_ref()).map(Metadata::from_inner)
+    }
+
+    /// Removes the file `path`, relative to this directory.
+    #[unstable(feature = "dir_fd", issue = "0")]
+    pub fn unlink_at<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.0.unlink_at(path.as_ref())
+    }
+
+    /// Removes the empty directory `path`, relative to this directory.
+    #[unstable(feature = "dir_fd", issue = "0")]
+    pub fn rmdir_at<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.0.rmdir_at(path.as_ref())
+    }
+
+    /// Creates the directory `path`, relative to this directory, with the
+    /// permission bits `mode` (which are masked by the process' `umask`).
+    #[unstable(feature = "dir_fd", issue = "0")]
+    pub fn mkdir_at<P: AsRef<Path>>(&self, path: P, mode: u32)
+        -> io::Result<()>
+    {
+        self.0.mkdir_at(path.as_ref(), mode)
+    }
+
+    /// Renames `old`, relative to this directory, to `new`, relative to
+    /// `new_dir`.
+    ///
+    /// `flags` is either 0 or a combination of `RENAME_NOREPLACE` and
+    /// `RENAME_EXCHANGE`. Non-zero flags require Linux 3.15 or newer and a
+    /// filesystem that supports them.
+    #[unstable(feature = "dir_fd", issue = "0")]
+    pub fn rename_at<P: AsRef<Path>, Q: AsRef<Path>>(&self,
+                                                     old: P,
+                                                     new_dir: &Dir,
+                                                     new: Q,
+                                                     flags: u32)
+                                                     -> io::Result<()> {
+        self.0.rename_at(old.as_ref(), &new_dir.0, new.as_ref(), flags)
+    }
+
+    /// Creates a symbolic link `dst`, relative to this directory, pointing to
+    /// `src`.
+    #[unstable(feature = "dir_fd", issue = "0")]
+    pub fn symlink_at<P: AsRef<Path>, Q: AsRef<Path>>(&self, src: P, dst: Q)
+        -> io::Result<()>
+    {
+        self.0.symlink_at(src.as_ref(), dst.as_ref())
+    }
+
+    /// Returns an iterator over the entries of this directory.
+    ///
+    /// The paths returned by `DirEntry::path` are built from the path this
+    /// directory was opened with.
+    #[unstable(feature = "dir_fd", issue = "0")]
+    pub fn read_dir(&self) -> io::Result<ReadDir> {
+        self.0.read_dir().map(ReadDir::from_inner)
+    }
+}
+
+impl AsInner<sys::fs::Dir> for Dir {
+    fn as_inner(&self) -> &sys::fs::Dir { &self.0 }
+}
+
+#[unstable(feature = "dir_fd", issue = "0")]
+impl AsRawFd for Dir {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.fd().raw()
+    }
+}
+
+#[unstable(feature = "dir_fd", issue = "0")]
+impl FromRawFd for Dir {
+    unsafe fn from_raw_fd(fd: RawFd) -> Dir {
+        Dir(sys::fs::Dir::from_inner((fd, PathBuf::new())))
+    }
+}
+
+#[unstable(feature = "dir_fd", issue = "0")]
+impl IntoRawFd for Dir {
+    fn into_raw_fd(self) -> RawFd {
+        self.0.into_fd().into_raw()
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Extended file attributes.
+//!
+//! Extended attributes are name/value pairs attached to files and
+//! directories. Names are namespaced by a prefix such as `user.`,
+//! `trusted.` or `security.`; values are arbitrary bytes.
+//!
+//! Every operation comes in three flavours: the plain functions follow
+//! symlinks, the `l` prefixed ones operate on a symlink itself and the `f`
+//! prefixed ones operate on an open file.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! #![feature(xattr)]
+//! use std::os::linux::fs::xattr;
+//!
+//! # fn foo() -> std::io::Result<()> {
+//! try!(xattr::set("foo.txt", "user.origin", b"downloads"));
+//! for name in try!(xattr::list("foo.txt")) {
+//!     println!("{:?} = {:?}", name, try!(xattr::get("foo.txt", &name)));
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+#![unstable(feature = "xattr", issue = "0")]
+
+use cmp;
+use ffi::{OsStr, OsString};
+use io;
+use os::unix::ffi::OsStringExt;
+use os::unix::io::AsRawFd;
+use path::Path;
+use sys::fs::{self as fs_imp, XattrTarget};
+
+/// Returns the value of the extended attribute `name` of the file at `path`,
+/// following symlinks, or `None` if the file has no such attribute.
+pub fn get<P, N>(path: P, name: N) -> io::Result<Option<Vec<u8>>>
+    where P: AsRef<Path>, N: AsRef<OsStr>
+{
+    fs_imp::getxattr(&XattrTarget::Path(path.as_ref()), name.as_ref())
+}
+
+/// Like `get`, but returns the attribute of a symlink itself.
+pub fn lget<P, N>(path: P, name: N) -> io::Result<Option<Vec<u8>>>
+    where P: AsRef<Path>, N: AsRef<OsStr>
+{
+    fs_imp::getxattr(&XattrTarget::LinkPath(path.as_ref()), name.as_ref())
+}
+
+/// Like `get`, but returns the attribute of an open file.
+pub fn fget<F, N>(file: &F, name: N) -> io::Result<Option<Vec<u8>>>
+    where F: AsRawFd, N: AsRef<OsStr>
+{
+    fs_imp::getxattr(&XattrTarget::Fd(file.as_raw_fd()), name.as_ref())
+}
+
+/// Sets the extended attribute `name` of the file at `path` to `value`,
+/// following symlinks. The attribute is created if it doesn't exist yet.
+pub fn set<P, N>(path: P, name: N, value: &[u8]) -> io::Result<()>
+    where P: AsRef<Path>, N: AsRef<OsStr>
+{
+    fs_imp::setxattr(&XattrTarget::Path(path.as_ref()), name.as_ref(), value, 0)
+}
+
+/// Like `set`, but sets the attribute of a symlink itself.
+///
+/// Note that the kernel refuses `user.` attributes on symlinks.
+pub fn lset<P, N>(path: P, name: N, value: &[u8]) -> io::Result<()>
+    where P: AsRef<Path>, N: AsRef<OsStr>
+{
+    fs_imp::setxattr(&XattrTarget::LinkPath(path.as_ref()),
+                     name.as_ref(),
+                     value,
+                     0)
+}
+
+/// Like `set`, but sets the attribute of an open file.
+pub fn fset<F, N>(file: &F, name: N, value: &[u8]) -> io::Result<()>
+    where F: AsRawFd, N: AsRef<OsStr>
+{
+    fs_imp::setxattr(&XattrTarget::Fd(file.as_raw_fd()), name.as_ref(), value, 0)
+}
+
+/// Removes the extended attribute `name` of the file at `path`, following
+/// symlinks.
+///
+/// Fails with `ENODATA` if the file has no such attribute.
+pub fn remove<P, N>(path: P, name: N) -> io::Result<()>
+    where P: AsRef<Path>, N: AsRef<OsStr>
+{
+    fs_imp::removexattr(&XattrTarget::Path(path.as_ref()), name.as_ref())
+}
+
+/// Like `remove`, but removes the attribute of a symlink itself.
+pub fn lremove<P, N>(path: P, name: N) -> io::Result<()>
+    where P: AsRef<Path>, N: AsRef<OsStr>
+{
+    fs_imp::removexattr(&XattrTarget::LinkPath(path.as_ref()), name.as_ref())
+}
+
+/// Like `remove`, but removes the attribute of an open file.
+pub fn fremove<F, N>(file: &F, name: N) -> io::Result<()>
+    where F: AsRawFd, N: AsRef<OsStr>
+{
+    fs_imp::removexattr(&XattrTarget::Fd(file.as_raw_fd()), name.as_ref())
+}
+
+/// Returns an iterator over the names of the extended attributes of the file
+/// at `path`, following symlinks.
+///
+/// Only the attributes the caller is allowed to see are listed, e.g.
+/// `trusted.` attributes need `CAP_SYS_ADMIN`.
+pub fn list<P: AsRef<Path>>(path: P) -> io::Result<XAttrs> {
+    fs_imp::listxattr(&XattrTarget::Path(path.as_ref())).map(XAttrs::new)
+}
+
+/// Like `list`, but lists the attributes of a symlink itself.
+pub fn llist<P: AsRef<Path>>(path: P) -> io::Result<XAttrs> {
+    fs_imp::listxattr(&XattrTarget::LinkPath(path.as_ref())).map(XAttrs::new)
+}
+
+/// Like `list`, but lists the attributes of an open file.
+pub fn flist<F: AsRawFd>(file: &F) -> io::Result<XAttrs> {
+    fs_imp::listxattr(&XattrTarget::Fd(file.as_raw_fd())).map(XAttrs::new)
+}
+
+/// An iterator over the names of the extended attributes of a file.
+///
+/// This struct is created by `list`, `llist` and `flist`.
+#[derive(Debug)]
+pub struct XAttrs {
+    // NUL terminated names, as returned by the kernel
+    names: Vec<u8>,
+    pos: usize,
+}
+
+impl XAttrs {
+    fn new(names: Vec<u8>) -> XAttrs {
+        XAttrs { names: names, pos: 0 }
+    }
+}
+
+impl Iterator for XAttrs {
+    type Item = OsString;
+
+    fn next(&mut self) -> Option<OsString> {
+        let rest = &self.names[self.pos..];
+        if rest.is_empty() {
+            return None;
+        }
+        let len = rest.iter().position(|&b| b == 0).unwrap_or(rest.len());
+        self.pos += cmp::min(len + 1, rest.len());
+        Some(OsString::from_vec(rest[..len].to_vec()))
+    }
+}
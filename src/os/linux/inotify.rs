@@ -0,0 +1,248 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Filesystem change notifications.
+//!
+//! An `Inotify` instance watches files and directories and queues an event
+//! each time one of them changes in a way matching its watch mask, a bitwise
+//! OR of the `IN_*` constants. Watching a directory reports events for the
+//! directory itself and for the entries directly inside it; in the latter
+//! case the event carries the name of the entry.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! #![feature(inotify)]
+//! use std::os::linux::inotify::{Inotify, IN_CLOSE_WRITE, IN_MOVED_TO};
+//!
+//! # fn foo() -> std::io::Result<()> {
+//! let inotify = try!(Inotify::new());
+//! try!(inotify.add_watch("/etc/myapp", IN_CLOSE_WRITE | IN_MOVED_TO));
+//!
+//! let mut buffer = [0; 4096];
+//! loop {
+//!     for event in try!(inotify.read_events(&mut buffer)) {
+//!         if event.name.map_or(false, |name| name == "config.toml") {
+//!             println!("reloading");
+//!         }
+//!     }
+//! }
+//! # }
+//! ```
+
+#![unstable(feature = "inotify", issue = "0")]
+
+use libc;
+
+use ffi::OsStr;
+use fmt;
+use io;
+use mem;
+use os::unix::ffi::OsStrExt;
+use os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use path::Path;
+use ptr;
+use sys::inotify as imp;
+use sys_common::FromInner;
+
+/// Event: the file was read.
+pub const IN_ACCESS: u32 = libc::IN_ACCESS;
+/// Event: the file was written to.
+pub const IN_MODIFY: u32 = libc::IN_MODIFY;
+/// Event: the metadata of the file (permissions, timestamps, extended
+/// attributes, link count, owner, ...) changed.
+pub const IN_ATTRIB: u32 = libc::IN_ATTRIB;
+/// Event: a file opened for writing was closed.
+pub const IN_CLOSE_WRITE: u32 = libc::IN_CLOSE_WRITE;
+/// Event: a file not opened for writing was closed.
+pub const IN_CLOSE_NOWRITE: u32 = libc::IN_CLOSE_NOWRITE;
+/// Event: the file was opened.
+pub const IN_OPEN: u32 = libc::IN_OPEN;
+/// Event: an entry was moved out of the watched directory.
+pub const IN_MOVED_FROM: u32 = libc::IN_MOVED_FROM;
+/// Event: an entry was moved into the watched directory.
+pub const IN_MOVED_TO: u32 = libc::IN_MOVED_TO;
+/// Event: an entry was created in the watched directory.
+pub const IN_CREATE: u32 = libc::IN_CREATE;
+/// Event: an entry was deleted from the watched directory.
+pub const IN_DELETE: u32 = libc::IN_DELETE;
+/// Event: the watched file or directory itself was deleted.
+pub const IN_DELETE_SELF: u32 = libc::IN_DELETE_SELF;
+/// Event: the watched file or directory itself was moved.
+pub const IN_MOVE_SELF: u32 = libc::IN_MOVE_SELF;
+/// Event: either of the two close events.
+pub const IN_CLOSE: u32 = IN_CLOSE_WRITE | IN_CLOSE_NOWRITE;
+/// Event: either of the two move events.
+pub const IN_MOVE: u32 = IN_MOVED_FROM | IN_MOVED_TO;
+/// Event: all of the above.
+pub const IN_ALL_EVENTS: u32 = IN_ACCESS | IN_MODIFY | IN_ATTRIB | IN_CLOSE |
+                               IN_OPEN | IN_MOVE | IN_CREATE | IN_DELETE |
+                               IN_DELETE_SELF | IN_MOVE_SELF;
+
+/// Reported: the filesystem of the watched file was unmounted.
+pub const IN_UNMOUNT: u32 = libc::IN_UNMOUNT;
+/// Reported: the event queue overflowed and events were lost. The watch
+/// descriptor of such an event is -1.
+pub const IN_Q_OVERFLOW: u32 = libc::IN_Q_OVERFLOW;
+/// Reported: the watch was removed, either explicitly or because the file
+/// went away. No more events come from the watch.
+pub const IN_IGNORED: u32 = libc::IN_IGNORED;
+/// Reported: the entry the event is about is a directory.
+pub const IN_ISDIR: u32 = libc::IN_ISDIR;
+
+/// Watch option: fail with `ENOTDIR` unless the path is a directory.
+pub const IN_ONLYDIR: u32 = libc::IN_ONLYDIR;
+/// Watch option: don't follow the path if it's a symlink.
+pub const IN_DONT_FOLLOW: u32 = libc::IN_DONT_FOLLOW;
+/// Watch option: stop reporting events for entries once they have been
+/// unlinked from the watched directory.
+pub const IN_EXCL_UNLINK: u32 = libc::IN_EXCL_UNLINK;
+/// Watch option: add to the mask of an existing watch of the same file
+/// instead of replacing it.
+pub const IN_MASK_ADD: u32 = libc::IN_MASK_ADD;
+/// Watch option: remove the watch after its first event.
+pub const IN_ONESHOT: u32 = libc::IN_ONESHOT;
+
+/// Identifies a watch of an `Inotify` instance.
+///
+/// Adding a watch for a file that's already watched by the same instance
+/// returns the descriptor of the existing watch.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct WatchDescriptor(i32);
+
+/// An inotify instance, watching a set of files and directories.
+pub struct Inotify(imp::Inotify);
+
+impl Inotify {
+    /// Creates an instance with no watches, in blocking mode.
+    pub fn new() -> io::Result<Inotify> {
+        imp::Inotify::new().map(Inotify)
+    }
+
+    /// Starts watching `path` for the events in `mask`, or changes the
+    /// mask of the watch if `path` is already watched.
+    pub fn add_watch<P: AsRef<Path>>(&self, path: P, mask: u32)
+        -> io::Result<WatchDescriptor>
+    {
+        self.0.add_watch(path.as_ref(), mask).map(WatchDescriptor)
+    }
+
+    /// Stops watching a file. An `IN_IGNORED` event is queued for the watch.
+    pub fn rm_watch(&self, wd: WatchDescriptor) -> io::Result<()> {
+        self.0.rm_watch(wd.0)
+    }
+
+    /// Moves the instance into or out of nonblocking mode.
+    ///
+    /// In nonblocking mode, `read_events` fails with
+    /// `ErrorKind::WouldBlock` instead of waiting when no event is queued.
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.0.set_nonblocking(nonblocking)
+    }
+
+    /// Reads as many queued events as fit in `buffer`, waiting for one if
+    /// the queue is empty, and returns an iterator over them.
+    ///
+    /// Events are only ever read whole, so `buffer` must have room for at
+    /// least one event with the longest possible name, i.e. 16 + 256 bytes;
+    /// smaller buffers may fail with `EINVAL`.
+    pub fn read_events<'a>(&self, buffer: &'a mut [u8])
+        -> io::Result<Events<'a>>
+    {
+        let len = self.0.read(buffer)?;
+        Ok(Events { buffer: &buffer[..len], pos: 0 })
+    }
+}
+
+impl fmt::Debug for Inotify {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Inotify").field("fd", &self.as_raw_fd()).finish()
+    }
+}
+
+impl AsRawFd for Inotify {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.fd().raw()
+    }
+}
+
+impl FromRawFd for Inotify {
+    unsafe fn from_raw_fd(fd: RawFd) -> Inotify {
+        Inotify(imp::Inotify::from_inner(fd))
+    }
+}
+
+impl IntoRawFd for Inotify {
+    fn into_raw_fd(self) -> RawFd {
+        self.0.into_fd().into_raw()
+    }
+}
+
+/// An event read from an `Inotify` instance.
+#[derive(Clone, Copy, Debug)]
+pub struct Event<'a> {
+    /// The watch the event comes from.
+    pub wd: WatchDescriptor,
+    /// What happened, one of the event constants possibly combined with
+    /// `IN_ISDIR`, or one of `IN_UNMOUNT`, `IN_Q_OVERFLOW` and
+    /// `IN_IGNORED`.
+    pub mask: u32,
+    /// Links the `IN_MOVED_FROM` and `IN_MOVED_TO` events of a rename,
+    /// zero for other events.
+    pub cookie: u32,
+    /// The name of the entry the event is about, for events about an entry
+    /// of a watched directory.
+    pub name: Option<&'a OsStr>,
+}
+
+/// An iterator over the events read by `Inotify::read_events`.
+#[derive(Debug)]
+pub struct Events<'a> {
+    buffer: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Iterator for Events<'a> {
+    type Item = Event<'a>;
+
+    fn next(&mut self) -> Option<Event<'a>> {
+        let header = mem::size_of::<libc::inotify_event>();
+        if self.buffer.len() - self.pos < header {
+            return None;
+        }
+        // The kernel only writes whole events, but the buffer may not be
+        // aligned for `inotify_event`
+        let mut event: libc::inotify_event = unsafe { mem::zeroed() };
+        unsafe {
+            ptr::copy_nonoverlapping(self.buffer[self.pos..].as_ptr(),
+                                     &mut event as *mut _ as *mut u8,
+                                     header);
+        }
+        let start = self.pos + header;
+        let end = start + event.len as usize;
+        self.pos = end;
+        // The name is padded with NULs
+        let name = &self.buffer[start..end];
+        let name = match name.iter().position(|&b| b == 0) {
+            Some(len) => &name[..len],
+            None => name,
+        };
+        Some(Event {
+            wd: WatchDescriptor(event.wd),
+            mask: event.mask,
+            cookie: event.cookie,
+            name: if name.is_empty() {
+                None
+            } else {
+                Some(OsStr::from_bytes(name))
+            },
+        })
+    }
+}
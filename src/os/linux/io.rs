@@ -0,0 +1,132 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Moving data through pipes without copying it to userspace.
+//!
+//! `splice` moves data between a pipe and any other file descriptor, `tee`
+//! duplicates the contents of one pipe into another without consuming them,
+//! and `vmsplice` feeds memory into a pipe. In all cases at least one side
+//! has to be a pipe.
+//!
+//! `io::copy` already uses `splice` when one of its ends is a pipe and the
+//! other a `File` or a standard stream.
+//!
+//! # Examples
+//!
+//! Forward standard input to a log file, keeping a copy on standard output:
+//!
+//! ```no_run
+//! #![feature(splice)]
+//! use std::fs::OpenOptions;
+//! use std::io;
+//! use std::os::linux::io::{splice, tee, SPLICE_F_MOVE};
+//!
+//! # fn foo() -> io::Result<()> {
+//! let log = try!(OpenOptions::new().write(true).create(true).open("log"));
+//! let (stdin, stdout) = (io::stdin(), io::stdout());
+//! loop {
+//!     let n = try!(tee(&stdin, &stdout, 1 << 16, 0));
+//!     if n == 0 {
+//!         break;
+//!     }
+//!     let mut left = n;
+//!     while left > 0 {
+//!         let moved = try!(splice(&stdin, None, &log, None, left,
+//!                                 SPLICE_F_MOVE));
+//!         left -= moved;
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+#![unstable(feature = "splice", issue = "0")]
+
+use libc;
+
+use fs::File;
+use io::{self, IoSlice};
+use os::unix::io::{AsRawFd, FromRawFd};
+use sys::io as imp;
+
+/// Attempt to move pages instead of copying them. This is only a hint.
+pub const SPLICE_F_MOVE: u32 = libc::SPLICE_F_MOVE;
+/// Don't block on the pipes. The other file descriptor may still block
+/// unless it has been made nonblocking itself.
+pub const SPLICE_F_NONBLOCK: u32 = libc::SPLICE_F_NONBLOCK;
+/// More data will be coming in a subsequent call, like `MSG_MORE` for
+/// sockets.
+pub const SPLICE_F_MORE: u32 = libc::SPLICE_F_MORE;
+
+/// Creates a pipe, returning its read end and its write end.
+///
+/// Both ends have close-on-exec set.
+pub fn pipe() -> io::Result<(File, File)> {
+    let (read, write) = imp::pipe()?;
+    unsafe { Ok((File::from_raw_fd(read), File::from_raw_fd(write))) }
+}
+
+/// Moves up to `len` bytes from `fd_in` to `fd_out`, at least one of which
+/// must be a pipe.
+///
+/// For the side that isn't a pipe an offset can be given; the data is then
+/// read from or written to that position and the offset is advanced past
+/// it, leaving the file cursor alone. With `None` the file cursor is used
+/// and advanced instead. Passing an offset for a pipe is an error.
+///
+/// Returns the number of bytes moved, which is 0 at the end of the input.
+///
+/// # Errors
+///
+/// Fails with `EINVAL` if neither side is a pipe, or if the file system or
+/// the way `fd_out` was opened (e.g. `O_APPEND`) doesn't support splicing,
+/// and with `WouldBlock` if `SPLICE_F_NONBLOCK` was given and a pipe is
+/// empty or full.
+pub fn splice<R, W>(fd_in: &R,
+                    off_in: Option<&mut u64>,
+                    fd_out: &W,
+                    off_out: Option<&mut u64>,
+                    len: usize,
+                    flags: u32)
+                    -> io::Result<usize>
+    where R: AsRawFd + ?Sized, W: AsRawFd + ?Sized
+{
+    imp::splice(fd_in.as_raw_fd(),
+                off_in,
+                fd_out.as_raw_fd(),
+                off_out,
+                len,
+                flags)
+}
+
+/// Copies up to `len` bytes from the pipe `fd_in` to the pipe `fd_out`
+/// without consuming them, so that they can still be read from `fd_in`.
+///
+/// Returns the number of bytes copied, which is 0 if `fd_in` is empty and
+/// has no writers left.
+pub fn tee<R, W>(fd_in: &R, fd_out: &W, len: usize, flags: u32)
+                 -> io::Result<usize>
+    where R: AsRawFd + ?Sized, W: AsRawFd + ?Sized
+{
+    imp::tee(fd_in.as_raw_fd(), fd_out.as_raw_fd(), len, flags)
+}
+
+/// Writes the contents of `bufs` into the pipe `fd`, like `write_vectored`.
+///
+/// The kernel may reference the pages of `bufs` from the pipe instead of
+/// copying them, in which case changes made to the buffers before the data
+/// has been read from the pipe are visible to the reader.
+///
+/// Returns the number of bytes written, which may be less than the total
+/// length of `bufs` if the pipe fills up.
+pub fn vmsplice<W: AsRawFd + ?Sized>(fd: &W, bufs: &[IoSlice], flags: u32)
+                                     -> io::Result<usize> {
+    imp::vmsplice(fd.as_raw_fd(), bufs, flags)
+}
@@ -0,0 +1,443 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Asynchronous file I/O through io_uring.
+//!
+//! An `IoUring` queues operations on files for the kernel to carry out in
+//! the background. Each operation takes ownership of the buffers it needs
+//! and hands them back in its `Completion`, which carries the `Token`
+//! returned when the operation was queued. Operations on an open descriptor
+//! are submitted as they're queued, so the descriptor can be closed right
+//! after. Other operations don't reach the kernel until `submit` or `wait`
+//! is called.
+//!
+//! The operations here need Linux 5.6 or later. On older kernels
+//! `IoUring::new` fails with `ENOSYS`, and it fails with `EPERM` where
+//! io_uring has been disabled, so callers can fall back to blocking I/O.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! #![feature(io_uring)]
+//! use std::fs::File;
+//! use std::os::linux::io_uring::{IoUring, Outcome};
+//!
+//! # fn foo() -> std::io::Result<()> {
+//! let file = try!(File::open("/etc/hostname"));
+//! let mut ring = try!(IoUring::new(8));
+//! try!(ring.read(&file, vec![0; 64], 0));
+//! try!(ring.wait(1));
+//! for completion in ring.completions() {
+//!     if let Outcome::Read(result, buf) = completion.outcome {
+//!         try!(result);
+//!         println!("{}", String::from_utf8_lossy(&buf));
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+#![unstable(feature = "io_uring", issue = "0")]
+
+use libc;
+
+use cmp;
+use ffi::CString;
+use fmt;
+use fs::{File, Metadata, OpenOptions};
+use io;
+use mem;
+use os::linux::fs::Dir;
+use os::unix::ffi::OsStrExt;
+use os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use path::Path;
+use sys::errno;
+use sys::fd::FileDesc;
+use sys::fs::FileAttr;
+use sys::io_uring as imp;
+use sys_common::{AsInner, FromInner};
+
+/// An io_uring instance.
+///
+/// Dropping it cancels the operations in flight and waits for them to
+/// finish, since the kernel may use their buffers until then. Operations
+/// that can't be cancelled once started, like reads of regular files, are
+/// waited for.
+pub struct IoUring {
+    inner: imp::IoUring,
+    // Operations in flight, indexed by their token
+    ops: Vec<Option<Op>>,
+    free: Vec<usize>,
+    in_flight: usize,
+}
+
+// What an operation in flight owns, kept here until it completes since the
+// kernel may access it until then
+enum Op {
+    Read(Vec<u8>),
+    Write(Vec<u8>),
+    Fsync,
+    // The directory is looked up when the operation runs, so a duplicate of
+    // its descriptor is kept open
+    Openat(Option<FileDesc>, CString),
+    Statx(Option<FileDesc>, CString, Box<libc::statx>),
+    Close(FileDesc),
+}
+
+// The `user_data` of cancellations, which have no slot
+const CANCEL: u64 = !0;
+
+/// Identifies an operation in flight. Tokens are reused once the
+/// operation's completion has been returned.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Token(u64);
+
+/// A finished operation.
+#[derive(Debug)]
+pub struct Completion {
+    /// The token returned when the operation was queued.
+    pub token: Token,
+    /// The result of the operation, with anything it took ownership of.
+    pub outcome: Outcome,
+}
+
+/// The result of a finished operation.
+#[derive(Debug)]
+pub enum Outcome {
+    /// A `read`, with the number of bytes read and the buffer, truncated to
+    /// them if the read succeeded.
+    Read(io::Result<usize>, Vec<u8>),
+    /// A `write`, with the number of bytes written and the buffer.
+    Write(io::Result<usize>, Vec<u8>),
+    /// An `fsync`.
+    Fsync(io::Result<()>),
+    /// An `openat`, with the opened file.
+    Openat(io::Result<File>),
+    /// A `statx`, with the metadata of the file.
+    Statx(io::Result<Metadata>),
+    /// A `close`. The descriptor is closed even if this is an error.
+    Close(io::Result<()>),
+}
+
+impl IoUring {
+    /// Creates an instance that queues up to `entries` operations between
+    /// submissions, rounded up to a power of two. Up to twice as many can
+    /// be in flight.
+    ///
+    /// Fails with `ENOSYS` on kernels older than Linux 5.6, and with
+    /// `EINVAL` if `entries` is 0 or larger than 32768.
+    pub fn new(entries: u32) -> io::Result<IoUring> {
+        Ok(IoUring {
+            inner: imp::IoUring::new(entries)?,
+            ops: Vec::new(),
+            free: Vec::new(),
+            in_flight: 0,
+        })
+    }
+
+    /// Queues a read of up to `buf.len()` bytes at `offset` in `fd`, like
+    /// `FileExt::read_at`. For files without positions like pipes, or to
+    /// read at the file position and advance it, pass `u64::max_value()`.
+    ///
+    /// The operation is submitted right away, so `fd` may be closed once
+    /// this returns.
+    pub fn read<F: AsRawFd + ?Sized>(&mut self,
+                                     fd: &F,
+                                     mut buf: Vec<u8>,
+                                     offset: u64)
+                                     -> io::Result<Token> {
+        let mut sqe = sqe(libc::IORING_OP_READ, fd.as_raw_fd());
+        sqe.addr = buf.as_mut_ptr() as u64;
+        sqe.len = cmp::min(buf.len(), u32::max_value() as usize) as u32;
+        sqe.off = offset;
+        self.start(sqe, Op::Read(buf), true)
+    }
+
+    /// Queues a write of `buf` at `offset` in `fd`, like
+    /// `FileExt::write_at`. For files without positions like pipes, or to
+    /// write at the file position and advance it, pass `u64::max_value()`.
+    ///
+    /// The operation is submitted right away, so `fd` may be closed once
+    /// this returns.
+    pub fn write<F: AsRawFd + ?Sized>(&mut self,
+                                      fd: &F,
+                                      buf: Vec<u8>,
+                                      offset: u64)
+                                      -> io::Result<Token> {
+        let mut sqe = sqe(libc::IORING_OP_WRITE, fd.as_raw_fd());
+        sqe.addr = buf.as_ptr() as u64;
+        sqe.len = cmp::min(buf.len(), u32::max_value() as usize) as u32;
+        sqe.off = offset;
+        self.start(sqe, Op::Write(buf), true)
+    }
+
+    /// Queues flushing `fd` to disk, like `File::sync_all`, or like
+    /// `File::sync_data` if `datasync` is set.
+    ///
+    /// Operations aren't ordered, so this doesn't cover writes to `fd`
+    /// still in flight. Like `read`, it's submitted right away.
+    pub fn fsync<F: AsRawFd + ?Sized>(&mut self, fd: &F, datasync: bool)
+                                      -> io::Result<Token> {
+        let mut sqe = sqe(libc::IORING_OP_FSYNC, fd.as_raw_fd());
+        if datasync {
+            sqe.op_flags = libc::IORING_FSYNC_DATASYNC;
+        }
+        self.start(sqe, Op::Fsync, true)
+    }
+
+    /// Queues opening `path` with `opts`, relative to `dir` or to the
+    /// current directory if `dir` is `None`.
+    ///
+    /// The operation keeps its own descriptor of `dir`, so `dir` may be
+    /// dropped once this returns.
+    pub fn openat<P: AsRef<Path>>(&mut self,
+                                  dir: Option<&Dir>,
+                                  path: P,
+                                  opts: &OpenOptions)
+                                  -> io::Result<Token> {
+        let opts = opts.as_inner();
+        let path = CString::new(path.as_ref().as_os_str().as_bytes())?;
+        let dir = duplicate(dir)?;
+        let mut sqe = sqe(libc::IORING_OP_OPENAT, dirfd(&dir));
+        sqe.addr = path.as_ptr() as u64;
+        sqe.len = opts.open_mode() as u32;
+        sqe.op_flags = opts.open_flags()? as u32;
+        self.start(sqe, Op::Openat(dir, path), false)
+    }
+
+    /// Queues querying the metadata of `path`, relative to `dir` or to the
+    /// current directory if `dir` is `None`, following symbolic links.
+    ///
+    /// Like `openat`, this keeps its own descriptor of `dir`.
+    pub fn statx<P: AsRef<Path>>(&mut self, dir: Option<&Dir>, path: P)
+                                 -> io::Result<Token> {
+        let path = CString::new(path.as_ref().as_os_str().as_bytes())?;
+        let dir = duplicate(dir)?;
+        let mut statx: Box<libc::statx> = Box::new(unsafe { mem::zeroed() });
+        let mut sqe = sqe(libc::IORING_OP_STATX, dirfd(&dir));
+        sqe.addr = path.as_ptr() as u64;
+        sqe.len = libc::STATX_BASIC_STATS;
+        sqe.off = &mut *statx as *mut libc::statx as u64;
+        self.start(sqe, Op::Statx(dir, path, statx), false)
+    }
+
+    /// Queues closing `fd`.
+    pub fn close<F: IntoRawFd>(&mut self, fd: F) -> io::Result<Token> {
+        let fd = FileDesc::new(fd.into_raw_fd());
+        let sqe = sqe(libc::IORING_OP_CLOSE, fd.raw());
+        self.start(sqe, Op::Close(fd), false)
+    }
+
+    // Operations on a descriptor get `submit` set, as the kernel only looks
+    // it up on submission, by which time the caller may have closed it and
+    // the number may stand for another file
+    fn start(&mut self, mut sqe: libc::io_uring_sqe, op: Op, submit: bool)
+             -> io::Result<Token> {
+        // Every completion has to fit in the completion queue, or the
+        // kernel may drop it and leave the operation in flight forever
+        if self.in_flight == self.inner.cq_entries() as usize {
+            return Err(io::Error::new(io::ErrorKind::Other,
+                                      "too many operations in flight"));
+        }
+        let slot = self.free.last().cloned().unwrap_or(self.ops.len());
+        sqe.user_data = slot as u64;
+        unsafe {
+            if !self.inner.push(&sqe) {
+                // The kernel takes everything submitted off the queue
+                self.inner.enter(0)?;
+                if !self.inner.push(&sqe) {
+                    return Err(io::Error::new(io::ErrorKind::Other,
+                                              "submission queue is full"));
+                }
+            }
+        }
+        if submit {
+            let res = self.inner.enter(0);
+            // Submission stops early if the kernel runs out of memory
+            if self.inner.retract() {
+                return Err(res.err().unwrap_or_else(|| {
+                    io::Error::from_raw_os_error(errno::EAGAIN)
+                }));
+            }
+        }
+        if self.free.pop().is_none() {
+            self.ops.push(None);
+        }
+        self.ops[slot] = Some(op);
+        self.in_flight += 1;
+        Ok(Token(slot as u64))
+    }
+
+    /// Submits the queued operations without waiting for any to finish.
+    /// Returns how many were submitted.
+    pub fn submit(&mut self) -> io::Result<usize> {
+        self.inner.enter(0)
+    }
+
+    /// Submits the queued operations and waits until at least `n`
+    /// completions, or all operations in flight if there are fewer, are
+    /// ready to be taken from `completions`.
+    pub fn wait(&mut self, n: usize) -> io::Result<()> {
+        let n = cmp::min(n, self.in_flight) as u32;
+        loop {
+            // A signal can end the wait early, so wait for the rest
+            let ready = self.inner.ready();
+            self.inner.enter(n.saturating_sub(ready))?;
+            if self.inner.ready() >= n {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Returns an iterator over the completions that are ready, without
+    /// waiting for more.
+    pub fn completions(&mut self) -> Completions {
+        Completions { ring: self }
+    }
+
+    /// Returns the number of operations queued or submitted whose
+    /// completions haven't been returned yet.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight
+    }
+
+    fn complete(&mut self, cqe: libc::io_uring_cqe) -> Completion {
+        let slot = cqe.user_data as usize;
+        let op = self.ops[slot].take().expect("unknown io_uring completion");
+        self.free.push(slot);
+        self.in_flight -= 1;
+
+        let res = if cqe.res < 0 {
+            Err(io::Error::from_raw_os_error(-cqe.res))
+        } else {
+            Ok(cqe.res as usize)
+        };
+        let outcome = match op {
+            Op::Read(mut buf) => {
+                if let Ok(n) = res {
+                    buf.truncate(n);
+                }
+                Outcome::Read(res, buf)
+            }
+            Op::Write(buf) => Outcome::Write(res, buf),
+            Op::Fsync => Outcome::Fsync(res.map(|_| ())),
+            Op::Openat(..) => {
+                Outcome::Openat(res.map(|fd| unsafe {
+                    File::from_raw_fd(fd as RawFd)
+                }))
+            }
+            Op::Statx(_, _, statx) => {
+                Outcome::Statx(res.map(|_| {
+                    Metadata::from_inner(FileAttr::from_statx(&statx))
+                }))
+            }
+            Op::Close(fd) => {
+                // The kernel has closed it already
+                fd.into_raw();
+                Outcome::Close(res.map(|_| ()))
+            }
+        };
+        Completion { token: Token(slot as u64), outcome: outcome }
+    }
+}
+
+fn sqe(opcode: u8, fd: RawFd) -> libc::io_uring_sqe {
+    let mut sqe: libc::io_uring_sqe = unsafe { mem::zeroed() };
+    sqe.opcode = opcode;
+    sqe.fd = fd;
+    sqe
+}
+
+fn duplicate(dir: Option<&Dir>) -> io::Result<Option<FileDesc>> {
+    match dir {
+        Some(dir) => dir.as_inner().fd().duplicate().map(Some),
+        None => Ok(None),
+    }
+}
+
+fn dirfd(dir: &Option<FileDesc>) -> RawFd {
+    dir.as_ref().map_or(libc::AT_FDCWD, |fd| fd.raw())
+}
+
+impl Drop for IoUring {
+    fn drop(&mut self) {
+        // The kernel may still write to the buffers of operations in flight,
+        // so wait for all of them, after cancelling those that would
+        // otherwise block forever, like a read of a pipe nobody writes to
+        for slot in 0..self.ops.len() {
+            if self.ops[slot].is_none() {
+                continue;
+            }
+            let mut sqe = sqe(libc::IORING_OP_ASYNC_CANCEL, -1);
+            sqe.addr = slot as u64;
+            sqe.user_data = CANCEL;
+            // Points at nothing, so it's safe to submit
+            let pushed = unsafe {
+                self.inner.push(&sqe) ||
+                (self.inner.enter(0).is_ok() && self.inner.push(&sqe))
+            };
+            if !pushed {
+                break;
+            }
+        }
+        let _ = self.inner.enter(0);
+
+        while self.in_flight > 0 {
+            while let Some(cqe) = self.inner.pop() {
+                if cqe.user_data != CANCEL {
+                    self.complete(cqe);
+                }
+            }
+            if self.in_flight > 0 && self.inner.enter(1).is_err() {
+                // Leak the buffers rather than free them under the kernel
+                mem::forget(mem::replace(&mut self.ops, Vec::new()));
+                break;
+            }
+        }
+    }
+}
+
+impl fmt::Debug for IoUring {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("IoUring")
+            .field("fd", &self.as_raw_fd())
+            .field("in_flight", &self.in_flight)
+            .finish()
+    }
+}
+
+impl AsRawFd for IoUring {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.fd().raw()
+    }
+}
+
+/// An iterator over the completions that are ready, returned by
+/// `IoUring::completions`.
+pub struct Completions<'a> {
+    ring: &'a mut IoUring,
+}
+
+impl<'a> Iterator for Completions<'a> {
+    type Item = Completion;
+
+    fn next(&mut self) -> Option<Completion> {
+        match self.ring.inner.pop() {
+            Some(cqe) => Some(self.ring.complete(cqe)),
+            None => None,
+        }
+    }
+}
+
+impl<'a> fmt::Debug for Completions<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad("Completions { .. }")
+    }
+}
@@ -0,0 +1,277 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Memory mapped files and anonymous memory maps.
+//!
+//! # Truncation
+//!
+//! A file map is only backed by the file as long as the file is at least as
+//! long as the mapped range: touching a page that lies past the end of the
+//! file kills the process with `SIGBUS`.
+//!
+//! Creating a map fails if the range extends past the end of the file, but
+//! nothing stops the file from being truncated later on, by this or another
+//! process. Each file map keeps its own handle to the file so `check` can
+//! tell whether that has happened. Code that maps files other programs may
+//! shrink should call `check` before reading from a long lived map, or
+//! prevent truncation altogether, e.g. with a file lock all writers honor.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! #![feature(mmap)]
+//! use std::fs::File;
+//! use std::os::linux::mmap::{Advice, Mmap};
+//!
+//! # fn foo() -> std::io::Result<()> {
+//! let f = try!(File::open("index.dat"));
+//! let map = try!(Mmap::map(&f));
+//! try!(map.advise(Advice::Random));
+//! println!("magic: {:?}", &map[..4]);
+//! # Ok(())
+//! # }
+//! ```
+
+#![unstable(feature = "mmap", issue = "0")]
+
+use libc;
+
+use fmt;
+use fs::File;
+use io::{self, Error, ErrorKind};
+use ops::{Deref, DerefMut};
+use slice;
+use sys::mmap as imp;
+use sys_common::AsInner;
+
+/// How a map is going to be accessed, see `Mmap::advise`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Advice {
+    /// No special treatment, the default.
+    Normal,
+    /// Pages will be accessed in random order, read ahead is less useful.
+    Random,
+    /// Pages will be accessed in sequential order, read ahead aggressively
+    /// and drop pages soon after they've been accessed.
+    Sequential,
+    /// Pages will be accessed soon, start reading them in.
+    WillNeed,
+    /// Pages won't be accessed soon and can be dropped. The next access to
+    /// a page of a file map reads it back from the file and the next access
+    /// to a page of an anonymous map returns zeros.
+    DontNeed,
+}
+
+impl Advice {
+    fn to_raw(self) -> i32 {
+        match self {
+            Advice::Normal => libc::MADV_NORMAL,
+            Advice::Random => libc::MADV_RANDOM,
+            Advice::Sequential => libc::MADV_SEQUENTIAL,
+            Advice::WillNeed => libc::MADV_WILLNEED,
+            Advice::DontNeed => libc::MADV_DONTNEED,
+        }
+    }
+}
+
+fn file_len(file: &File) -> io::Result<usize> {
+    let len = file.metadata()?.len();
+    if len > usize::max_value() as u64 {
+        return Err(Error::new(ErrorKind::InvalidInput,
+                              "file is too large to be mapped"));
+    }
+    Ok(len as usize)
+}
+
+/// A read-only memory map of a file.
+///
+/// The map dereferences to a byte slice. Writes to the file, by this or
+/// other processes, are visible through the map.
+pub struct Mmap(imp::Mmap);
+
+impl Mmap {
+    /// Maps the whole file, which must be open for reading and can't be
+    /// empty.
+    pub fn map(file: &File) -> io::Result<Mmap> {
+        Mmap::map_range(file, 0, file_len(file)?)
+    }
+
+    /// Maps `len` bytes of the file starting at `offset`.
+    ///
+    /// Fails with `InvalidInput` if `len` is zero or the range extends past
+    /// the end of the file.
+    pub fn map_range(file: &File, offset: u64, len: usize)
+        -> io::Result<Mmap>
+    {
+        imp::Mmap::file(file.as_inner(), offset, len, false).map(Mmap)
+    }
+
+    /// Tells the kernel how the map is going to be accessed.
+    pub fn advise(&self, advice: Advice) -> io::Result<()> {
+        self.0.advise(advice.to_raw())
+    }
+
+    /// Locks the pages of the map into memory, reading them in if needed.
+    ///
+    /// Fails with `ENOMEM` if that would go over `RLIMIT_MEMLOCK`.
+    pub fn lock(&self) -> io::Result<()> {
+        self.0.lock()
+    }
+
+    /// Unlocks the pages of the map, letting them be paged out again.
+    pub fn unlock(&self) -> io::Result<()> {
+        self.0.unlock()
+    }
+
+    /// Checks that the file still covers the whole map.
+    ///
+    /// Fails with `UnexpectedEof` if the file was truncated since it was
+    /// mapped, in which case accessing the tail of the map would kill the
+    /// process with `SIGBUS`. See the module documentation.
+    pub fn check(&self) -> io::Result<()> {
+        self.0.check()
+    }
+}
+
+impl Deref for Mmap {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.0.ptr(), self.0.len()) }
+    }
+}
+
+impl AsRef<[u8]> for Mmap {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl fmt::Debug for Mmap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Mmap")
+            .field("ptr", &self.0.ptr())
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
+
+unsafe impl Send for Mmap {}
+unsafe impl Sync for Mmap {}
+
+/// A writable memory map, of a file or of anonymous memory.
+///
+/// File maps are shared: writes through the map end up in the file, though
+/// only `flush` guarantees when. Anonymous maps are private to the process
+/// and start out zeroed.
+pub struct MmapMut(imp::Mmap);
+
+impl MmapMut {
+    /// Maps the whole file, which must be open for reading and writing and
+    /// can't be empty.
+    pub fn map_mut(file: &File) -> io::Result<MmapMut> {
+        MmapMut::map_range_mut(file, 0, file_len(file)?)
+    }
+
+    /// Maps `len` bytes of the file starting at `offset`.
+    ///
+    /// Fails with `InvalidInput` if `len` is zero or the range extends past
+    /// the end of the file.
+    pub fn map_range_mut(file: &File, offset: u64, len: usize)
+        -> io::Result<MmapMut>
+    {
+        imp::Mmap::file(file.as_inner(), offset, len, true).map(MmapMut)
+    }
+
+    /// Maps `len` bytes of zeroed anonymous memory.
+    pub fn map_anon(len: usize) -> io::Result<MmapMut> {
+        imp::Mmap::anon(len).map(MmapMut)
+    }
+
+    /// Writes the modified pages of the map back to the file and waits for
+    /// the writes to complete. Does nothing for anonymous maps.
+    pub fn flush(&self) -> io::Result<()> {
+        self.0.flush(0, self.0.len(), true)
+    }
+
+    /// Like `flush`, but only starts the writes without waiting for them.
+    pub fn flush_async(&self) -> io::Result<()> {
+        self.0.flush(0, self.0.len(), false)
+    }
+
+    /// Like `flush`, but only for `len` bytes starting at `offset` in the
+    /// map.
+    pub fn flush_range(&self, offset: usize, len: usize) -> io::Result<()> {
+        self.0.flush(offset, len, true)
+    }
+
+    /// Tells the kernel how the map is going to be accessed.
+    pub fn advise(&self, advice: Advice) -> io::Result<()> {
+        self.0.advise(advice.to_raw())
+    }
+
+    /// Locks the pages of the map into memory, reading them in if needed.
+    ///
+    /// Fails with `ENOMEM` if that would go over `RLIMIT_MEMLOCK`.
+    pub fn lock(&self) -> io::Result<()> {
+        self.0.lock()
+    }
+
+    /// Unlocks the pages of the map, letting them be paged out again.
+    pub fn unlock(&self) -> io::Result<()> {
+        self.0.unlock()
+    }
+
+    /// Checks that the file still covers the whole map. Always succeeds for
+    /// anonymous maps.
+    ///
+    /// See `Mmap::check`.
+    pub fn check(&self) -> io::Result<()> {
+        self.0.check()
+    }
+}
+
+impl Deref for MmapMut {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.0.ptr(), self.0.len()) }
+    }
+}
+
+impl DerefMut for MmapMut {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        unsafe { slice::from_raw_parts_mut(self.0.ptr(), self.0.len()) }
+    }
+}
+
+impl AsRef<[u8]> for MmapMut {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl AsMut<[u8]> for MmapMut {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self
+    }
+}
+
+impl fmt::Debug for MmapMut {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MmapMut")
+            .field("ptr", &self.0.ptr())
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
+
+unsafe impl Send for MmapMut {}
+unsafe impl Sync for MmapMut {}
@@ -13,4 +13,22 @@
 #![stable(feature = "raw_ext", since = "1.1.0")]
 
 pub mod raw;
+pub mod epoll;
+pub mod eventfd;
 pub mod fs;
+pub mod inotify;
+pub mod io;
+pub mod io_uring;
+pub mod mmap;
+pub mod ns;
+pub mod process;
+pub mod pty;
+pub mod resource;
+pub mod seccomp;
+pub mod termios;
+pub mod timerfd;
+
+#[unstable(feature = "eventfd", issue = "0")]
+pub use self::eventfd::EventFd;
+#[unstable(feature = "timerfd", issue = "0")]
+pub use self::timerfd::TimerFd;
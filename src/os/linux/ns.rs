@@ -0,0 +1,261 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Namespaces, and the mount and root directory changes that go with them.
+//!
+//! A namespace gives the processes in it their own view of a global
+//! resource, like the mount table or the process IDs. `unshare` moves this
+//! process into new namespaces, a bitwise OR of the `CLONE_NEW*` constants,
+//! and `setns` into an existing one, opened from `/proc/<pid>/ns/`.
+//!
+//! Creating a user namespace needs no privileges, and the new namespace
+//! grants all capabilities over the other namespaces it owns. Its user and
+//! group IDs are mapped to those outside with `write_uid_map` and
+//! `write_gid_map`.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! #![feature(ns)]
+//! use std::fs;
+//! use std::os::linux::ns::{self, IdMap};
+//! use std::os::unix::fs::MetadataExt;
+//!
+//! # fn foo() -> std::io::Result<()> {
+//! let uid = try!(fs::metadata("/proc/self")).uid();
+//! try!(ns::unshare(ns::CLONE_NEWUSER | ns::CLONE_NEWNS));
+//! let root = IdMap { inside: 0, outside: uid, count: 1 };
+//! try!(ns::write_uid_map(0, &[root]));
+//!
+//! // Keep the mounts below from showing up outside
+//! try!(ns::mount("none", "/", None, ns::MS_REC | ns::MS_PRIVATE, None));
+//! try!(ns::mount("tmpfs", "/mnt", Some("tmpfs"), 0, Some("size=1m")));
+//! # Ok(())
+//! # }
+//! ```
+
+#![unstable(feature = "ns", issue = "0")]
+
+use libc;
+
+use ffi::{CString, OsStr};
+use io;
+use os::unix::ffi::OsStrExt;
+use os::unix::io::AsRawFd;
+use path::Path;
+use sys::ns as imp;
+
+/// Namespace: cgroup root directory. Needs Linux 4.6.
+pub const CLONE_NEWCGROUP: i32 = libc::CLONE_NEWCGROUP;
+/// Namespace: System V IPC objects and POSIX message queues.
+pub const CLONE_NEWIPC: i32 = libc::CLONE_NEWIPC;
+/// Namespace: network devices, addresses, ports, routes and firewall
+/// rules.
+pub const CLONE_NEWNET: i32 = libc::CLONE_NEWNET;
+/// Namespace: mounts. The new namespace starts out with a copy of the
+/// mounts of the old one.
+pub const CLONE_NEWNS: i32 = libc::CLONE_NEWNS;
+/// Namespace: process IDs. `unshare` doesn't move the calling process, but
+/// its next child, which becomes PID 1 of the new namespace.
+pub const CLONE_NEWPID: i32 = libc::CLONE_NEWPID;
+/// Namespace: the boot-time and monotonic clocks. Like `CLONE_NEWPID`, it
+/// applies to children only. Needs Linux 5.6.
+pub const CLONE_NEWTIME: i32 = libc::CLONE_NEWTIME;
+/// Namespace: user and group IDs and capabilities. Fails with `EINVAL` if
+/// the process has more than one thread.
+pub const CLONE_NEWUSER: i32 = libc::CLONE_NEWUSER;
+/// Namespace: host name and NIS domain name.
+pub const CLONE_NEWUTS: i32 = libc::CLONE_NEWUTS;
+
+/// Mount flag: read-only.
+pub const MS_RDONLY: u64 = libc::MS_RDONLY as u64;
+/// Mount flag: ignore set-user-ID and set-group-ID bits and file
+/// capabilities.
+pub const MS_NOSUID: u64 = libc::MS_NOSUID as u64;
+/// Mount flag: don't allow access to device files.
+pub const MS_NODEV: u64 = libc::MS_NODEV as u64;
+/// Mount flag: don't allow programs to be executed.
+pub const MS_NOEXEC: u64 = libc::MS_NOEXEC as u64;
+/// Mount flag: don't update access times.
+pub const MS_NOATIME: u64 = libc::MS_NOATIME as u64;
+/// Mount flag: don't update access times of directories.
+pub const MS_NODIRATIME: u64 = libc::MS_NODIRATIME as u64;
+/// Mount flag: update access times only if they're older than the
+/// modification or change time, or a day old.
+pub const MS_RELATIME: u64 = libc::MS_RELATIME as u64;
+/// Mount flag: always update access times.
+pub const MS_STRICTATIME: u64 = libc::MS_STRICTATIME as u64;
+/// Operation: change the flags and data of the existing mount at the
+/// target.
+pub const MS_REMOUNT: u64 = libc::MS_REMOUNT as u64;
+/// Operation: make the file or directory at the source also visible at
+/// the target. The file system type and data are ignored.
+pub const MS_BIND: u64 = libc::MS_BIND as u64;
+/// Operation: move the mount at the source to the target.
+pub const MS_MOVE: u64 = libc::MS_MOVE as u64;
+/// Operation: change the propagation of the mount at the target so that
+/// mounts under it aren't propagated to or from other mounts.
+pub const MS_PRIVATE: u64 = libc::MS_PRIVATE as u64;
+/// Operation: like `MS_PRIVATE`, but mounts are still received from the
+/// mounts it was shared with.
+pub const MS_SLAVE: u64 = libc::MS_SLAVE as u64;
+/// Operation: share mounts under the target with the mounts it's copied
+/// to, in both directions.
+pub const MS_SHARED: u64 = libc::MS_SHARED as u64;
+/// Operation: like `MS_PRIVATE`, and the mount also can't be bind mounted.
+pub const MS_UNBINDABLE: u64 = libc::MS_UNBINDABLE as u64;
+/// Option: apply `MS_BIND` or a propagation change to the mounts below
+/// too.
+pub const MS_REC: u64 = libc::MS_REC as u64;
+
+/// Unmount flag: abort pending requests, for network file systems.
+pub const MNT_FORCE: i32 = libc::MNT_FORCE;
+/// Unmount flag: detach the mount right away and unmount it once it's no
+/// longer busy.
+pub const MNT_DETACH: i32 = libc::MNT_DETACH;
+/// Unmount flag: mark the mount as expired, or unmount it if it already
+/// was and hasn't been used since.
+pub const MNT_EXPIRE: i32 = libc::MNT_EXPIRE;
+/// Unmount flag: don't follow the target if it's a symbolic link.
+pub const UMOUNT_NOFOLLOW: i32 = libc::UMOUNT_NOFOLLOW;
+
+/// Moves this process into new namespaces, a bitwise OR of the
+/// `CLONE_NEW*` constants.
+///
+/// Needs `CAP_SYS_ADMIN`, except for `CLONE_NEWUSER` and namespaces
+/// created along with it.
+pub fn unshare(flags: i32) -> io::Result<()> {
+    imp::unshare(flags)
+}
+
+/// Moves this process into the namespace `fd` refers to, opened from
+/// `/proc/<pid>/ns/`. `nstype` is the `CLONE_NEW*` constant it should be,
+/// or 0 for any.
+pub fn setns<F: AsRawFd + ?Sized>(fd: &F, nstype: i32) -> io::Result<()> {
+    imp::setns(fd.as_raw_fd(), nstype)
+}
+
+/// A range of IDs in a user namespace and the IDs they stand for outside.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct IdMap {
+    /// The first ID in the namespace.
+    pub inside: u32,
+    /// The first ID it stands for outside, in the namespace of the process
+    /// writing the map.
+    pub outside: u32,
+    /// The number of IDs.
+    pub count: u32,
+}
+
+/// Sets the user ID map of the user namespace of the process `pid`, 0
+/// meaning this one. It can only be set once.
+///
+/// Without `CAP_SETUID` in the parent namespace, only the effective user ID
+/// of the process that created the namespace can be mapped.
+pub fn write_uid_map(pid: u32, map: &[IdMap]) -> io::Result<()> {
+    imp::write_proc(&proc_path(pid, "uid_map")?, &id_map(map))
+}
+
+/// Sets the group ID map of the user namespace of the process `pid`, 0
+/// meaning this one. It can only be set once.
+///
+/// Without `CAP_SETGID` in the parent namespace, only the effective group
+/// ID of the process that created the namespace can be mapped, and only
+/// after `deny_setgroups`.
+pub fn write_gid_map(pid: u32, map: &[IdMap]) -> io::Result<()> {
+    imp::write_proc(&proc_path(pid, "gid_map")?, &id_map(map))
+}
+
+/// Stops the processes in the user namespace of the process `pid`, 0
+/// meaning this one, from calling `setgroups`, which could otherwise drop
+/// groups that deny access. Needs Linux 3.19.
+pub fn deny_setgroups(pid: u32) -> io::Result<()> {
+    imp::write_proc(&proc_path(pid, "setgroups")?, b"deny")
+}
+
+// `/proc/self/<file>` for a `pid` of 0
+fn proc_path(pid: u32, file: &str) -> io::Result<CString> {
+    let path = if pid == 0 {
+        format!("/proc/self/{}", file)
+    } else {
+        format!("/proc/{}/{}", pid, file)
+    };
+    Ok(CString::new(path)?)
+}
+
+fn id_map(map: &[IdMap]) -> Vec<u8> {
+    imp::id_map(map.iter().map(|r| (r.inside, r.outside, r.count)))
+}
+
+fn cstr<S: AsRef<OsStr> + ?Sized>(s: &S) -> io::Result<CString> {
+    Ok(CString::new(s.as_ref().as_bytes())?)
+}
+
+/// Mounts `source` at the directory or file `target`.
+///
+/// `source` is usually a device or, with `MS_BIND`, a path, and is ignored
+/// by some file systems and operations, so can be anything then, e.g.
+/// `"none"`. `fstype` is the file system type, like `"tmpfs"` or `"proc"`,
+/// and `data` its options, separated by commas. `flags` is a bitwise OR of
+/// the `MS_*` constants, of which only one operation can be given.
+///
+/// Needs `CAP_SYS_ADMIN` in the user namespace owning the mount namespace.
+pub fn mount<S, T>(source: S,
+                   target: T,
+                   fstype: Option<&str>,
+                   flags: u64,
+                   data: Option<&str>)
+                   -> io::Result<()>
+    where S: AsRef<OsStr>, T: AsRef<Path>
+{
+    let source = cstr(source.as_ref())?;
+    let target = cstr(target.as_ref())?;
+    let fstype = match fstype {
+        Some(fstype) => Some(cstr(fstype)?),
+        None => None,
+    };
+    let data = match data {
+        Some(data) => Some(cstr(data)?),
+        None => None,
+    };
+    imp::mount(&source,
+               &target,
+               fstype.as_ref().map(|s| &**s),
+               flags as libc::c_ulong,
+               data.as_ref().map(|s| &**s))
+}
+
+/// Unmounts the topmost mount at `target`. `flags` is a bitwise OR of the
+/// `MNT_*` constants and `UMOUNT_NOFOLLOW`.
+///
+/// Fails with `EBUSY` if the mount is in use, unless `MNT_DETACH` is given.
+pub fn umount2<P: AsRef<Path>>(target: P, flags: i32) -> io::Result<()> {
+    imp::umount2(&cstr(target.as_ref())?, flags)
+}
+
+/// Makes the mount at `new_root` the root mount of this mount namespace,
+/// and moves the old root mount to `put_old`, which has to be below
+/// `new_root`. The old root can then be unmounted with `MNT_DETACH`.
+///
+/// Processes whose root or current directory was the old root are moved to
+/// the new one. Fails with `EINVAL` if either mount is shared, see
+/// `MS_PRIVATE`.
+pub fn pivot_root<N, O>(new_root: N, put_old: O) -> io::Result<()>
+    where N: AsRef<Path>, O: AsRef<Path>
+{
+    imp::pivot_root(&cstr(new_root.as_ref())?, &cstr(put_old.as_ref())?)
+}
+
+/// Changes the root directory of this process, which absolute paths are
+/// resolved from, to `path`. The current directory is left as it is, so
+/// may be outside of the new root.
+pub fn chroot<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    imp::chroot(&cstr(path.as_ref())?)
+}
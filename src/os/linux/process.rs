@@ -0,0 +1,144 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Linux-specific extensions to primitives in the `std::process` module.
+//!
+//! The functions here change attributes of the calling process through
+//! `prctl`. Some of them only apply to the calling thread, as noted.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! #![feature(linux_process_ext)]
+//! use std::os::linux::process;
+//!
+//! # fn foo() -> std::io::Result<()> {
+//! // Adopt orphaned descendants, so they can be waited for
+//! try!(process::set_child_subreaper(true));
+//! try!(process::set_name("supervisor"));
+//! # Ok(())
+//! # }
+//! ```
+
+#![unstable(feature = "linux_process_ext", issue = "0")]
+
+use libc;
+
+use ffi::{CStr, CString, OsStr, OsString};
+use io;
+use os::unix::ffi::{OsStrExt, OsStringExt};
+use sys::prctl as imp;
+use time::Duration;
+
+/// Sets the name of the calling thread, which is shown by `ps` and in
+/// `/proc/self/comm`. Names longer than 15 bytes are truncated.
+///
+/// Fails with `ErrorKind::InvalidInput` if `name` contains a NUL byte.
+pub fn set_name<S: AsRef<OsStr>>(name: S) -> io::Result<()> {
+    let name = CString::new(name.as_ref().as_bytes())?;
+    imp::set_name(&name)
+}
+
+/// Returns the name of the calling thread. It starts out as the first 15
+/// bytes of the file name of the program.
+pub fn name() -> io::Result<OsString> {
+    let mut buf = [0; 16];
+    imp::get_name(&mut buf)?;
+    let name = unsafe { CStr::from_ptr(buf.as_ptr() as *const libc::c_char) };
+    Ok(OsString::from_vec(name.to_bytes().to_vec()))
+}
+
+/// Sets the signal the calling thread receives when the thread that created
+/// it exits, or stops it from receiving one if `signal` is `None`.
+///
+/// The setting is cleared in children and when executing a set-user-ID or
+/// set-group-ID program. Fails with `EINVAL` if `signal` isn't a valid
+/// signal number.
+pub fn set_parent_death_signal(signal: Option<i32>) -> io::Result<()> {
+    imp::set_pdeathsig(signal.unwrap_or(0))
+}
+
+/// Returns the signal the calling thread receives when its parent exits.
+pub fn parent_death_signal() -> io::Result<Option<i32>> {
+    imp::get_pdeathsig().map(|signal| {
+        if signal == 0 { None } else { Some(signal) }
+    })
+}
+
+/// Stops this process and its children from gaining privileges through
+/// `exec`, e.g. from set-user-ID programs or file capabilities. It can't be
+/// undone.
+///
+/// This is required for installing a seccomp filter without
+/// `CAP_SYS_ADMIN`.
+pub fn set_no_new_privs() -> io::Result<()> {
+    imp::set_flag(libc::PR_SET_NO_NEW_PRIVS, true)
+}
+
+/// Returns whether `set_no_new_privs` is in effect.
+pub fn no_new_privs() -> io::Result<bool> {
+    imp::get_flag(libc::PR_GET_NO_NEW_PRIVS)
+}
+
+/// Sets whether this process can dump core and be attached to with
+/// `ptrace` by processes of the same user.
+///
+/// The flag is reset when executing a program or changing credentials.
+pub fn set_dumpable(dumpable: bool) -> io::Result<()> {
+    imp::set_flag(libc::PR_SET_DUMPABLE, dumpable)
+}
+
+/// Returns whether this process can dump core.
+pub fn dumpable() -> io::Result<bool> {
+    imp::get_flag(libc::PR_GET_DUMPABLE)
+}
+
+/// Makes this process a subreaper: orphaned descendants are reparented to
+/// it rather than to init, so it can wait for them.
+///
+/// The setting isn't inherited by children, but is kept when executing a
+/// program.
+pub fn set_child_subreaper(subreaper: bool) -> io::Result<()> {
+    imp::set_flag(libc::PR_SET_CHILD_SUBREAPER, subreaper)
+}
+
+/// Returns whether this process is a subreaper.
+pub fn child_subreaper() -> io::Result<bool> {
+    imp::get_child_subreaper()
+}
+
+/// Sets how much the kernel may delay the timers of the calling thread, to
+/// group wakeups and save power. A `slack` of 0 restores the default, the
+/// slack of the thread that created this one.
+///
+/// Slack above `u64::max_value()` nanoseconds is clamped.
+pub fn set_timer_slack(slack: Duration) -> io::Result<()> {
+    imp::set_timerslack(nanos(slack))
+}
+
+/// Returns how much the kernel may delay the timers of the calling thread.
+pub fn timer_slack() -> io::Result<Duration> {
+    imp::get_timerslack().map(|nsecs| {
+        let nsecs = nsecs as u64;
+        Duration::new(nsecs / 1_000_000_000, (nsecs % 1_000_000_000) as u32)
+    })
+}
+
+fn nanos(d: Duration) -> libc::c_ulong {
+    let nsecs = d.as_secs()
+        .checked_mul(1_000_000_000)
+        .and_then(|n| n.checked_add(d.subsec_nanos() as u64))
+        .unwrap_or(u64::max_value());
+    if nsecs > libc::c_ulong::max_value() as u64 {
+        libc::c_ulong::max_value()
+    } else {
+        nsecs as libc::c_ulong
+    }
+}
@@ -0,0 +1,77 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Pseudo-terminals.
+//!
+//! A pseudo-terminal is a pair of connected files. A program using the
+//! slave sees a terminal, while what it writes can be read from the master
+//! and what is written to the master is its input, after the usual terminal
+//! processing like echo and line editing. Programs are usually started with
+//! the slave as their standard streams and controlling terminal.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! #![feature(pty)]
+//! use std::io::{Read, Write};
+//! use std::os::linux::pty;
+//!
+//! # fn foo() -> std::io::Result<()> {
+//! let mut pty = try!(pty::openpty(None));
+//! try!(pty.master.write_all(b"hello\n"));
+//! let mut line = [0; 6];
+//! try!(pty.slave.read_exact(&mut line));
+//! assert_eq!(&line, b"hello\n");
+//! # Ok(())
+//! # }
+//! ```
+
+#![unstable(feature = "pty", issue = "0")]
+
+use fs::File;
+use io;
+use os::linux::termios::{self, WindowSize};
+use os::unix::io::AsRawFd;
+use path::PathBuf;
+use sys::fs::File as FileImp;
+use sys::pty as imp;
+use sys_common::FromInner;
+
+/// The two ends of a pseudo-terminal, returned by `openpty`.
+#[derive(Debug)]
+pub struct Pty {
+    /// The end controlling the terminal.
+    pub master: File,
+    /// The terminal itself.
+    pub slave: File,
+}
+
+/// Opens a new pseudo-terminal, with the window size `size` if one is
+/// given. Neither end becomes the controlling terminal of this process.
+///
+/// Needs the devpts file system mounted on /dev/pts, with /dev/ptmx
+/// pointing into it.
+pub fn openpty(size: Option<WindowSize>) -> io::Result<Pty> {
+    let (master, slave) = imp::openpty()?;
+    let pty = Pty {
+        master: File::from_inner(FileImp::from_inner(master.into_raw())),
+        slave: File::from_inner(FileImp::from_inner(slave.into_raw())),
+    };
+    if let Some(size) = size {
+        termios::set_window_size(&pty.slave, size)?;
+    }
+    Ok(pty)
+}
+
+/// Returns the path of the slave belonging to the master `master`, like
+/// `/dev/pts/3`.
+pub fn ptsname<F: AsRawFd + ?Sized>(master: &F) -> io::Result<PathBuf> {
+    imp::ptsname(master.as_raw_fd())
+}
@@ -0,0 +1,181 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Resource limits and usage.
+//!
+//! Every resource has a soft limit, which the kernel enforces, and a hard
+//! limit, the ceiling for the soft limit. Any process may lower its limits,
+//! but raising the hard limit needs `CAP_SYS_RESOURCE`. Limits are
+//! inherited by child processes.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! #![feature(resource)]
+//! use std::os::linux::resource::{self, Limit, Resource, Who};
+//!
+//! # fn foo() -> std::io::Result<()> {
+//! let limit = try!(resource::get_limit(Resource::Nofile));
+//! try!(resource::set_limit(Resource::Nofile,
+//!                          Limit { soft: limit.hard, hard: limit.hard }));
+//!
+//! let usage = try!(resource::getrusage(Who::Process));
+//! println!("{:?} of CPU time so far", usage.user_time + usage.system_time);
+//! # Ok(())
+//! # }
+//! ```
+
+#![unstable(feature = "resource", issue = "0")]
+
+use libc;
+
+use io;
+use sys::resource as imp;
+use time::Duration;
+
+/// A resource whose use can be limited, named after its `RLIMIT_*`
+/// constant.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Resource {
+    /// CPU time in seconds. `SIGXCPU` is sent when the soft limit is
+    /// reached and `SIGKILL` at the hard limit.
+    Cpu = libc::RLIMIT_CPU as isize,
+    /// The size in bytes a file may grow to. Writing past it raises
+    /// `SIGXFSZ`.
+    Fsize = libc::RLIMIT_FSIZE as isize,
+    /// The size in bytes of the data segment.
+    Data = libc::RLIMIT_DATA as isize,
+    /// The size in bytes of the main thread's stack.
+    Stack = libc::RLIMIT_STACK as isize,
+    /// The size in bytes of core dumps, 0 to disable them.
+    Core = libc::RLIMIT_CORE as isize,
+    /// Has no effect since Linux 2.6.
+    Rss = libc::RLIMIT_RSS as isize,
+    /// The number of processes and threads of the real user ID.
+    Nproc = libc::RLIMIT_NPROC as isize,
+    /// One more than the highest file descriptor that can be opened.
+    Nofile = libc::RLIMIT_NOFILE as isize,
+    /// The number of bytes that can be locked in memory.
+    Memlock = libc::RLIMIT_MEMLOCK as isize,
+    /// The size in bytes of the address space.
+    As = libc::RLIMIT_AS as isize,
+    /// Has no effect since Linux 2.4.25.
+    Locks = libc::RLIMIT_LOCKS as isize,
+    /// The number of signals that can be queued for the real user ID.
+    Sigpending = libc::RLIMIT_SIGPENDING as isize,
+    /// The number of bytes in POSIX message queues of the real user ID.
+    Msgqueue = libc::RLIMIT_MSGQUEUE as isize,
+    /// The ceiling of the nice value, as `20 - limit`.
+    Nice = libc::RLIMIT_NICE as isize,
+    /// The ceiling of the real-time priority.
+    Rtprio = libc::RLIMIT_RTPRIO as isize,
+    /// CPU time in microseconds a real-time thread may use without
+    /// blocking.
+    Rttime = libc::RLIMIT_RTTIME as isize,
+}
+
+/// The soft and hard limit of a resource. `None` is unlimited.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Limit {
+    /// The limit the kernel enforces.
+    pub soft: Option<u64>,
+    /// The ceiling for the soft limit.
+    pub hard: Option<u64>,
+}
+
+/// Returns the limit of `resource` for this process.
+pub fn get_limit(resource: Resource) -> io::Result<Limit> {
+    prlimit(0, resource, None)
+}
+
+/// Sets the limit of `resource` for this process.
+///
+/// Fails with `EINVAL` if the soft limit is above the hard limit, and with
+/// `EPERM` when raising the hard limit without `CAP_SYS_RESOURCE`.
+pub fn set_limit(resource: Resource, limit: Limit) -> io::Result<()> {
+    prlimit(0, resource, Some(limit)).map(|_| ())
+}
+
+/// Returns the limit of `resource` for the process `pid`, 0 meaning this
+/// one, and sets it to `new` if given.
+///
+/// Needs the same user IDs as the process, or `CAP_SYS_RESOURCE`.
+pub fn prlimit(pid: u32, resource: Resource, new: Option<Limit>)
+               -> io::Result<Limit> {
+    let new = new.map(|new| imp::rlimit(new.soft, new.hard));
+    let old = imp::prlimit(pid as libc::pid_t,
+                           resource as libc::c_uint,
+                           new.as_ref())?;
+    Ok(Limit {
+        soft: imp::finite(old.rlim_cur),
+        hard: imp::finite(old.rlim_max),
+    })
+}
+
+/// Whose usage `getrusage` returns.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Who {
+    /// All threads of this process.
+    Process,
+    /// The children of this process that have terminated and been waited
+    /// for, and their waited-for descendants.
+    Children,
+    /// The calling thread.
+    Thread,
+}
+
+/// Resource usage, as returned by `getrusage`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Usage {
+    /// CPU time spent in user mode.
+    pub user_time: Duration,
+    /// CPU time spent in the kernel.
+    pub system_time: Duration,
+    /// The peak resident set size in kilobytes. For `Who::Children`, that
+    /// of the largest child.
+    pub max_rss: u64,
+    /// Page faults served without I/O.
+    pub minor_faults: u64,
+    /// Page faults that needed I/O.
+    pub major_faults: u64,
+    /// Reads from block devices.
+    pub block_inputs: u64,
+    /// Writes to block devices.
+    pub block_outputs: u64,
+    /// Context switches from waiting for a resource.
+    pub voluntary_switches: u64,
+    /// Context switches from running out of time or being preempted.
+    pub involuntary_switches: u64,
+}
+
+/// Returns the resource usage of `who`.
+pub fn getrusage(who: Who) -> io::Result<Usage> {
+    let who = match who {
+        Who::Process => libc::RUSAGE_SELF,
+        Who::Children => libc::RUSAGE_CHILDREN,
+        Who::Thread => libc::RUSAGE_THREAD,
+    };
+    let ru = imp::getrusage(who)?;
+    Ok(Usage {
+        user_time: duration(ru.ru_utime),
+        system_time: duration(ru.ru_stime),
+        max_rss: ru.ru_maxrss as u64,
+        minor_faults: ru.ru_minflt as u64,
+        major_faults: ru.ru_majflt as u64,
+        block_inputs: ru.ru_inblock as u64,
+        block_outputs: ru.ru_oublock as u64,
+        voluntary_switches: ru.ru_nvcsw as u64,
+        involuntary_switches: ru.ru_nivcsw as u64,
+    })
+}
+
+fn duration(tv: libc::timeval) -> Duration {
+    Duration::new(tv.tv_sec as u64, tv.tv_usec as u32 * 1000)
+}
@@ -0,0 +1,303 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Seccomp filters, which restrict the syscalls a thread may make.
+//!
+//! A `Filter` is a list of rules, each matching a syscall number and
+//! optionally conditions on its arguments, with the `Action` taken when it
+//! matches. Syscalls that no rule matches get the default action of the
+//! filter, so an allow list starts out from `Action::Kill` or
+//! `Action::Errno` and a deny list from `Action::Allow`. Syscall numbers
+//! are those of the target, found in `nr`.
+//!
+//! Syscalls made through another ABI than that of the target, like i386
+//! syscalls on x86_64, always kill the process.
+//!
+//! An installed filter can't be removed and is inherited by new threads
+//! and child processes. Installing another filter adds to it, and the
+//! action of the most restrictive filter is taken.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! #![feature(seccomp)]
+//! use std::os::linux::seccomp::{nr, Action, Arg, Cmp, Filter};
+//!
+//! # fn foo() -> std::io::Result<()> {
+//! let mut filter = Filter::new(Action::Kill);
+//! filter.allow(nr::READ)
+//!       .allow(nr::EXIT_GROUP)
+//!       // Only to stdout and stderr
+//!       .rule(nr::WRITE, &[Arg::new(0, Cmp::Le, 2)], Action::Allow)
+//!       .rule(nr::OPENAT, &[], Action::Errno(13));
+//! try!(filter.install());
+//! # Ok(())
+//! # }
+//! ```
+
+#![unstable(feature = "seccomp", issue = "0")]
+
+use libc;
+
+use io;
+use sys::errno;
+use sys::seccomp as imp;
+
+/// The syscall numbers of the target.
+pub use sc::nr;
+
+/// What happens to a syscall matched by a rule.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Action {
+    /// The syscall is made.
+    Allow,
+    /// The syscall fails with this `errno`, of which only the low 16 bits
+    /// are kept.
+    Errno(i32),
+    /// The process is killed as if by `SIGSYS`. Before Linux 4.14 only the
+    /// calling thread is killed.
+    Kill,
+    /// The calling thread is killed as if by `SIGSYS`.
+    KillThread,
+    /// The calling thread receives `SIGSYS` and the syscall isn't made.
+    Trap,
+    /// A `ptrace` tracer is notified with this value, or the syscall fails
+    /// with `ENOSYS` if there's none.
+    Trace(u16),
+    /// The syscall is made and logged. Needs Linux 4.14.
+    Log,
+}
+
+/// How `Arg` compares a syscall argument with a value, as unsigned 64-bit
+/// integers.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Cmp {
+    /// The argument equals the value.
+    Eq,
+    /// The argument doesn't equal the value.
+    Ne,
+    /// The argument is less than the value.
+    Lt,
+    /// The argument is less than or equal to the value.
+    Le,
+    /// The argument is greater than the value.
+    Gt,
+    /// The argument is greater than or equal to the value.
+    Ge,
+    /// The argument, masked with this, equals the value.
+    MaskedEq(u64),
+}
+
+/// A condition on a syscall argument.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Arg {
+    index: u32,
+    cmp: Cmp,
+    value: u64,
+}
+
+impl Arg {
+    /// Compares argument `index`, counting from 0, with `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is 6 or more, as syscalls take at most 6
+    /// arguments.
+    pub fn new(index: u32, cmp: Cmp, value: u64) -> Arg {
+        assert!(index < 6, "syscalls take at most 6 arguments");
+        Arg {
+            index: index,
+            cmp: cmp,
+            value: value,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Rule {
+    nr: usize,
+    args: Vec<Arg>,
+    action: Action,
+}
+
+/// A seccomp filter, compiled into a BPF program when installed.
+#[derive(Clone, Debug)]
+pub struct Filter {
+    default: Action,
+    rules: Vec<Rule>,
+}
+
+impl Filter {
+    /// Creates a filter without rules, taking `default` on every syscall.
+    pub fn new(default: Action) -> Filter {
+        Filter {
+            default: default,
+            rules: Vec::new(),
+        }
+    }
+
+    /// Allows syscall `nr`.
+    pub fn allow(&mut self, nr: usize) -> &mut Filter {
+        self.rule(nr, &[], Action::Allow)
+    }
+
+    /// Makes syscall `nr` fail with `EPERM`.
+    pub fn deny(&mut self, nr: usize) -> &mut Filter {
+        self.rule(nr, &[], Action::Errno(errno::EPERM))
+    }
+
+    /// Takes `action` on syscall `nr` if all of `args` hold.
+    ///
+    /// Rules are tried in the order they were added and the first one that
+    /// matches is taken.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `args` has more than 16 conditions.
+    pub fn rule(&mut self, nr: usize, args: &[Arg], action: Action)
+                -> &mut Filter {
+        // Keeps the jumps out of a rule within the 255 instructions BPF
+        // allows
+        assert!(args.len() <= 16, "a rule takes at most 16 conditions");
+        self.rules.push(Rule {
+            nr: nr,
+            args: args.to_vec(),
+            action: action,
+        });
+        self
+    }
+
+    /// Installs this filter for the calling thread, after setting
+    /// `os::linux::process::set_no_new_privs`.
+    ///
+    /// Fails with `EINVAL` if the filter is too large, more than 4096
+    /// instructions of which each rule takes up to 3 and each condition up
+    /// to 6.
+    pub fn install(&self) -> io::Result<()> {
+        imp::install(&self.compile())
+    }
+
+    fn compile(&self) -> Vec<libc::sock_filter> {
+        let mut filter = Vec::new();
+        imp::prologue(&mut filter);
+        // Whether the accumulator holds the syscall number
+        let mut loaded = false;
+        for rule in &self.rules {
+            if !loaded {
+                filter.push(imp::stmt(LD, imp::NR));
+            }
+            // Instructions that jump past the rule when a condition fails,
+            // with whether they do so on true
+            let mut fails = Vec::new();
+            fails.push((filter.len(), false));
+            filter.push(imp::jump(JEQ, rule.nr as u32, 0, 0));
+            for arg in &rule.args {
+                compare(&mut filter, &mut fails, arg);
+            }
+            filter.push(imp::stmt(RET, ret(rule.action)));
+
+            let end = filter.len();
+            for (i, on_true) in fails {
+                let offset = (end - i - 1) as u8;
+                if on_true {
+                    filter[i].jt = offset;
+                } else {
+                    filter[i].jf = offset;
+                }
+            }
+            loaded = rule.args.is_empty();
+        }
+        filter.push(imp::stmt(RET, ret(self.default)));
+        filter
+    }
+}
+
+const LD: u16 = libc::BPF_LD | libc::BPF_W | libc::BPF_ABS;
+const AND: u16 = libc::BPF_ALU | libc::BPF_AND | libc::BPF_K;
+const JEQ: u16 = libc::BPF_JMP | libc::BPF_JEQ | libc::BPF_K;
+const JGT: u16 = libc::BPF_JMP | libc::BPF_JGT | libc::BPF_K;
+const JGE: u16 = libc::BPF_JMP | libc::BPF_JGE | libc::BPF_K;
+const RET: u16 = libc::BPF_RET | libc::BPF_K;
+
+fn ret(action: Action) -> u32 {
+    match action {
+        Action::Allow => libc::SECCOMP_RET_ALLOW,
+        Action::Errno(errno) => {
+            libc::SECCOMP_RET_ERRNO | (errno as u32 & libc::SECCOMP_RET_DATA)
+        }
+        Action::Kill => libc::SECCOMP_RET_KILL_PROCESS,
+        Action::KillThread => libc::SECCOMP_RET_KILL_THREAD,
+        Action::Trap => libc::SECCOMP_RET_TRAP,
+        Action::Trace(data) => libc::SECCOMP_RET_TRACE | data as u32,
+        Action::Log => libc::SECCOMP_RET_LOG,
+    }
+}
+
+// BPF only works on 32 bits, so the argument is compared half by half.
+// Jumps of 0 are fixed up through `fails` to leave the rule.
+fn compare(filter: &mut Vec<libc::sock_filter>,
+           fails: &mut Vec<(usize, bool)>,
+           arg: &Arg) {
+    let (lo, hi) = imp::arg(arg.index);
+    let value_lo = arg.value as u32;
+    let value_hi = (arg.value >> 32) as u32;
+    let mut fail = |filter: &mut Vec<libc::sock_filter>, on_true| {
+        fails.push((filter.len(), on_true));
+    };
+
+    match arg.cmp {
+        Cmp::Eq => {
+            filter.push(imp::stmt(LD, hi));
+            fail(filter, false);
+            filter.push(imp::jump(JEQ, value_hi, 0, 0));
+            filter.push(imp::stmt(LD, lo));
+            fail(filter, false);
+            filter.push(imp::jump(JEQ, value_lo, 0, 0));
+        }
+        Cmp::Ne => {
+            filter.push(imp::stmt(LD, hi));
+            filter.push(imp::jump(JEQ, value_hi, 0, 2));
+            filter.push(imp::stmt(LD, lo));
+            fail(filter, true);
+            filter.push(imp::jump(JEQ, value_lo, 0, 0));
+        }
+        Cmp::Gt | Cmp::Ge => {
+            let jump = if arg.cmp == Cmp::Gt { JGT } else { JGE };
+            filter.push(imp::stmt(LD, hi));
+            filter.push(imp::jump(JGT, value_hi, 3, 0));
+            fail(filter, false);
+            filter.push(imp::jump(JEQ, value_hi, 0, 0));
+            filter.push(imp::stmt(LD, lo));
+            fail(filter, false);
+            filter.push(imp::jump(jump, value_lo, 0, 0));
+        }
+        Cmp::Lt | Cmp::Le => {
+            // Fails when the opposite of `Ge` or `Gt` holds
+            let jump = if arg.cmp == Cmp::Lt { JGE } else { JGT };
+            filter.push(imp::stmt(LD, hi));
+            fail(filter, true);
+            filter.push(imp::jump(JGT, value_hi, 0, 0));
+            filter.push(imp::jump(JEQ, value_hi, 0, 2));
+            filter.push(imp::stmt(LD, lo));
+            fail(filter, true);
+            filter.push(imp::jump(jump, value_lo, 0, 0));
+        }
+        Cmp::MaskedEq(mask) => {
+            filter.push(imp::stmt(LD, hi));
+            filter.push(imp::stmt(AND, (mask >> 32) as u32));
+            fail(filter, false);
+            filter.push(imp::jump(JEQ, value_hi, 0, 0));
+            filter.push(imp::stmt(LD, lo));
+            filter.push(imp::stmt(AND, mask as u32));
+            fail(filter, false);
+            filter.push(imp::jump(JEQ, value_lo, 0, 0));
+        }
+    }
+}
@@ -0,0 +1,249 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Terminal settings and window size.
+//!
+//! `Termios` holds the settings of a terminal, read with `Termios::get` and
+//! applied with `Termios::set`. `raw_mode` and `echo_off` change the
+//! settings for as long as the guard they return is alive, which makes sure
+//! the terminal is left usable even on early returns.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! #![feature(termios)]
+//! use std::io::{self, Read, Write};
+//! use std::os::linux::termios;
+//!
+//! # fn foo() -> io::Result<()> {
+//! print!("Password: ");
+//! try!(io::stdout().flush());
+//! let mut password = [0; 128];
+//! let stdin = io::stdin();
+//! let n = {
+//!     let _guard = try!(termios::echo_off(&stdin));
+//!     try!(io::stdin().read(&mut password))
+//! };
+//! let password = &password[..n];
+//!
+//! let size = try!(termios::window_size(&io::stdout()));
+//! println!("{} columns", size.cols);
+//! # Ok(())
+//! # }
+//! ```
+
+#![unstable(feature = "termios", issue = "0")]
+
+use libc;
+
+use fmt;
+use io;
+use marker::PhantomData;
+use os::unix::io::{AsRawFd, RawFd};
+use sys::termios as imp;
+
+/// The settings of a terminal.
+#[derive(Clone, Copy)]
+pub struct Termios(libc::termios);
+
+/// When `Termios::set` takes effect.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum When {
+    /// Right away.
+    Now,
+    /// Once all output written so far has been transmitted.
+    Drain,
+    /// Once all output written so far has been transmitted, also
+    /// discarding input that hasn't been read yet.
+    Flush,
+}
+
+impl Termios {
+    /// Reads the settings of the terminal `fd`.
+    ///
+    /// Fails with `ENOTTY` if `fd` isn't a terminal.
+    pub fn get<F: AsRawFd + ?Sized>(fd: &F) -> io::Result<Termios> {
+        imp::tcgetattr(fd.as_raw_fd()).map(Termios)
+    }
+
+    /// Applies these settings to the terminal `fd`.
+    ///
+    /// Succeeds if any of the settings could be applied, so read them back
+    /// with `get` to check those that matter.
+    pub fn set<F: AsRawFd + ?Sized>(&self, fd: &F, when: When)
+                                    -> io::Result<()> {
+        set(fd.as_raw_fd(), &self.0, when)
+    }
+
+    /// Switches to raw mode: input is available byte by byte, without line
+    /// editing, echo or signals for control characters, and output is
+    /// passed through unchanged.
+    pub fn make_raw(&mut self) {
+        imp::make_raw(&mut self.0)
+    }
+
+    /// Returns whether input is echoed.
+    pub fn echo(&self) -> bool {
+        self.0.c_lflag & libc::ECHO != 0
+    }
+
+    /// Turns echoing input on or off.
+    pub fn set_echo(&mut self, echo: bool) {
+        set_flag(&mut self.0.c_lflag, libc::ECHO, echo)
+    }
+
+    /// Returns whether input is line-buffered and can be edited before it
+    /// is read.
+    pub fn canonical(&self) -> bool {
+        self.0.c_lflag & libc::ICANON != 0
+    }
+
+    /// Turns line-buffered input on or off.
+    pub fn set_canonical(&mut self, canonical: bool) {
+        set_flag(&mut self.0.c_lflag, libc::ICANON, canonical)
+    }
+}
+
+fn set(fd: RawFd, termios: &libc::termios, when: When) -> io::Result<()> {
+    let cmd = match when {
+        When::Now => libc::TCSETS,
+        When::Drain => libc::TCSETSW,
+        When::Flush => libc::TCSETSF,
+    };
+    imp::tcsetattr(fd, cmd, termios)
+}
+
+fn set_flag(flags: &mut libc::tcflag_t, flag: libc::tcflag_t, on: bool) {
+    if on {
+        *flags |= flag;
+    } else {
+        *flags &= !flag;
+    }
+}
+
+impl fmt::Debug for Termios {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Termios")
+            .field("iflag", &self.0.c_iflag)
+            .field("oflag", &self.0.c_oflag)
+            .field("cflag", &self.0.c_cflag)
+            .field("lflag", &self.0.c_lflag)
+            .finish()
+    }
+}
+
+/// Restores the settings of a terminal when dropped, returned by
+/// `raw_mode` and `echo_off`.
+///
+/// The guard borrows the terminal, which therefore stays open until the
+/// guard is dropped.
+pub struct TermiosGuard<'a> {
+    fd: RawFd,
+    saved: Termios,
+    _terminal: PhantomData<&'a ()>,
+}
+
+impl<'a> TermiosGuard<'a> {
+    /// Returns the settings that will be restored.
+    pub fn saved(&self) -> &Termios {
+        &self.saved
+    }
+}
+
+impl<'a> Drop for TermiosGuard<'a> {
+    fn drop(&mut self) {
+        // Nothing sensible can be done about errors here
+        let _ = set(self.fd, &self.saved.0, When::Drain);
+    }
+}
+
+impl<'a> fmt::Debug for TermiosGuard<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TermiosGuard")
+            .field("fd", &self.fd)
+            .field("saved", &self.saved)
+            .finish()
+    }
+}
+
+fn change<'a, F, C>(fd: &'a F, f: C) -> io::Result<TermiosGuard<'a>>
+    where F: AsRawFd + ?Sized, C: FnOnce(&mut Termios)
+{
+    let saved = Termios::get(fd)?;
+    let mut termios = saved;
+    f(&mut termios);
+    termios.set(fd, When::Drain)?;
+    Ok(TermiosGuard {
+        fd: fd.as_raw_fd(),
+        saved: saved,
+        _terminal: PhantomData,
+    })
+}
+
+/// Switches the terminal `fd` to raw mode, see `Termios::make_raw`, until
+/// the returned guard is dropped.
+pub fn raw_mode<'a, F>(fd: &'a F) -> io::Result<TermiosGuard<'a>>
+    where F: AsRawFd + ?Sized
+{
+    change(fd, |t| t.make_raw())
+}
+
+/// Stops the terminal `fd` from echoing input until the returned guard is
+/// dropped, e.g. while reading a password. The newline ending a line is
+/// still echoed.
+pub fn echo_off<'a, F>(fd: &'a F) -> io::Result<TermiosGuard<'a>>
+    where F: AsRawFd + ?Sized
+{
+    change(fd, |t| {
+        t.set_echo(false);
+        set_flag(&mut t.0.c_lflag, libc::ECHONL, true);
+    })
+}
+
+/// The size of a terminal window.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct WindowSize {
+    /// The number of rows of characters.
+    pub rows: u16,
+    /// The number of columns of characters.
+    pub cols: u16,
+    /// The width in pixels, usually 0 because it's not known.
+    pub x_pixels: u16,
+    /// The height in pixels, usually 0 because it's not known.
+    pub y_pixels: u16,
+}
+
+/// Returns the window size of the terminal `fd`.
+///
+/// Fails with `ENOTTY` if `fd` isn't a terminal.
+pub fn window_size<F: AsRawFd + ?Sized>(fd: &F) -> io::Result<WindowSize> {
+    imp::window_size(fd.as_raw_fd()).map(|ws| {
+        WindowSize {
+            rows: ws.ws_row,
+            cols: ws.ws_col,
+            x_pixels: ws.ws_xpixel,
+            y_pixels: ws.ws_ypixel,
+        }
+    })
+}
+
+/// Sets the window size of the terminal `fd`, e.g. the slave of a
+/// pseudo-terminal when the window it's shown in is resized. The foreground
+/// process group of the terminal is sent `SIGWINCH` if the size changed.
+pub fn set_window_size<F: AsRawFd + ?Sized>(fd: &F, size: WindowSize)
+                                            -> io::Result<()> {
+    let ws = libc::winsize {
+        ws_row: size.rows,
+        ws_col: size.cols,
+        ws_xpixel: size.x_pixels,
+        ws_ypixel: size.y_pixels,
+    };
+    imp::set_window_size(fd.as_raw_fd(), &ws)
+}
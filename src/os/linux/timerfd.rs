@@ -0,0 +1,224 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Timers that can be waited on like any file descriptor.
+//!
+//! A `TimerFd` expires once after a delay or at a deadline, and then
+//! optionally at a fixed interval. It is readable for `poll` and `epoll`
+//! while expirations are pending, and `wait` returns how many happened
+//! since the last call.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! #![feature(timerfd)]
+//! use std::os::linux::timerfd::{Clock, TimerFd};
+//! use std::time::Duration;
+//!
+//! # fn foo() -> std::io::Result<()> {
+//! let timer = try!(TimerFd::new(Clock::Monotonic));
+//! try!(timer.set(Duration::from_millis(100),
+//!                Some(Duration::from_millis(100))));
+//! loop {
+//!     let missed = try!(timer.wait()) - 1;
+//!     println!("tick, {} missed", missed);
+//! }
+//! # }
+//! ```
+
+#![unstable(feature = "timerfd", issue = "0")]
+
+use libc;
+
+use fmt;
+use io;
+use os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd};
+use sys::timerfd as imp;
+use sys_common::{AsInner, FromInner};
+use time::{Duration, Instant, SystemTime};
+
+/// The clock a timer follows.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Clock {
+    /// The clock of `Instant`, which doesn't jump and stops while the
+    /// system is suspended.
+    Monotonic,
+    /// The clock of `SystemTime`, which follows changes to the system time.
+    Realtime,
+    /// Like `Monotonic`, but keeps going while the system is suspended.
+    Boottime,
+}
+
+/// A timerfd timer.
+pub struct TimerFd {
+    inner: imp::TimerFd,
+    // Unknown for timers made from a raw file descriptor
+    clock: Option<Clock>,
+}
+
+impl TimerFd {
+    /// Creates a disarmed timer following `clock`.
+    pub fn new(clock: Clock) -> io::Result<TimerFd> {
+        let id = match clock {
+            Clock::Monotonic => libc::CLOCK_MONOTONIC,
+            Clock::Realtime => libc::CLOCK_REALTIME,
+            Clock::Boottime => libc::CLOCK_BOOTTIME,
+        };
+        Ok(TimerFd {
+            inner: imp::TimerFd::new(id)?,
+            clock: Some(clock),
+        })
+    }
+
+    /// Arms the timer to expire once after `value`, and then every
+    /// `interval` if one is given. Replaces any previous setting and
+    /// discards pending expirations.
+    ///
+    /// A zero `value` makes the timer expire right away.
+    pub fn set(&self, value: Duration, interval: Option<Duration>)
+               -> io::Result<()> {
+        self.inner.settime(false,
+                           value_timespec(value),
+                           interval_timespec(interval))
+    }
+
+    /// Arms the timer to expire at `deadline`, and then every `interval` if
+    /// one is given. Replaces any previous setting.
+    ///
+    /// A deadline in the past makes the timer expire right away, counting
+    /// the intervals that have already elapsed.
+    ///
+    /// `Instant` follows the monotonic clock, so this fails with
+    /// `ErrorKind::InvalidInput` for timers following another clock.
+    pub fn set_at(&self, deadline: Instant, interval: Option<Duration>)
+                  -> io::Result<()> {
+        self.check_clock(Clock::Monotonic)?;
+        let deadline = *deadline.as_inner().as_inner();
+        self.inner.settime(true, deadline, interval_timespec(interval))
+    }
+
+    /// Arms the timer to expire at `deadline`, and then every `interval` if
+    /// one is given. Replaces any previous setting.
+    ///
+    /// The deadline stays fixed if the system time is changed, so the timer
+    /// may expire earlier or later than `deadline` would suggest at the time
+    /// of the call.
+    ///
+    /// `SystemTime` follows the realtime clock, so this fails with
+    /// `ErrorKind::InvalidInput` for timers following another clock.
+    pub fn set_at_system_time(&self,
+                              deadline: SystemTime,
+                              interval: Option<Duration>)
+                              -> io::Result<()> {
+        self.check_clock(Clock::Realtime)?;
+        let deadline = *deadline.as_inner().as_inner();
+        self.inner.settime(true, deadline, interval_timespec(interval))
+    }
+
+    /// Disarms the timer. Like setting it, this discards pending
+    /// expirations.
+    pub fn disarm(&self) -> io::Result<()> {
+        let zero = imp::to_timespec(Duration::new(0, 0));
+        self.inner.settime(false, zero, zero)
+    }
+
+    /// Returns the time left until the timer next expires, or `None` if
+    /// it's disarmed.
+    pub fn remaining(&self) -> io::Result<Option<Duration>> {
+        self.inner.gettime().map(|t| nonzero(imp::from_timespec(t.it_value)))
+    }
+
+    /// Returns when the timer next expires, or `None` if it's disarmed.
+    ///
+    /// For timers following another clock than `Clock::Monotonic`, this is
+    /// when the timer expires if the system time isn't changed and the
+    /// system isn't suspended in the meantime.
+    pub fn next_expiry(&self) -> io::Result<Option<Instant>> {
+        let now = Instant::now();
+        self.remaining().map(|remaining| remaining.map(|d| now + d))
+    }
+
+    /// Returns the interval the timer repeats at, or `None` if it only
+    /// expires once.
+    pub fn interval(&self) -> io::Result<Option<Duration>> {
+        self.inner.gettime().map(|t| nonzero(imp::from_timespec(t.it_interval)))
+    }
+
+    /// Waits until the timer has expired at least once, and returns the
+    /// number of expirations since the timer was set or last waited for.
+    ///
+    /// In nonblocking mode, fails with `ErrorKind::WouldBlock` instead of
+    /// waiting.
+    pub fn wait(&self) -> io::Result<u64> {
+        self.inner.read()
+    }
+
+    /// Moves the timer into or out of nonblocking mode.
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.inner.set_nonblocking(nonblocking)
+    }
+
+    // Timers made from a raw file descriptor are trusted to follow `clock`
+    fn check_clock(&self, clock: Clock) -> io::Result<()> {
+        match self.clock {
+            Some(c) if c != clock => {
+                Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                   "the deadline is on another clock than \
+                                    the timer"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+// A zero value disarms the timer, so bump it to the smallest one that
+// doesn't
+fn value_timespec(value: Duration) -> libc::timespec {
+    if value == Duration::new(0, 0) {
+        imp::to_timespec(Duration::new(0, 1))
+    } else {
+        imp::to_timespec(value)
+    }
+}
+
+fn interval_timespec(interval: Option<Duration>) -> libc::timespec {
+    imp::to_timespec(interval.unwrap_or(Duration::new(0, 0)))
+}
+
+fn nonzero(d: Duration) -> Option<Duration> {
+    if d == Duration::new(0, 0) { None } else { Some(d) }
+}
+
+impl fmt::Debug for TimerFd {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TimerFd").field("fd", &self.as_raw_fd()).finish()
+    }
+}
+
+impl AsRawFd for TimerFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.fd().raw()
+    }
+}
+
+impl FromRawFd for TimerFd {
+    unsafe fn from_raw_fd(fd: RawFd) -> TimerFd {
+        TimerFd {
+            inner: imp::TimerFd::from_inner(fd),
+            clock: None,
+        }
+    }
+}
+
+impl IntoRawFd for TimerFd {
+    fn into_raw_fd(self) -> RawFd {
+        self.inner.into_fd().into_raw()
+    }
+}
@@ -47,6 +47,19 @@ impl Stack {
     fn argv(&self) -> *const *const u8 {
         &self.argv0 as *const &'static u8 as *const *const u8
     }
+
+    // The auxiliary vector comes right after the NULL that terminates the
+    // environment, which in turn comes right after the NULL that terminates
+    // `argv`
+    fn auxv(&self) -> *const usize {
+        unsafe {
+            let mut envp = self.argv().offset(self.argc + 1);
+            while !(*envp).is_null() {
+                envp = envp.offset(1);
+            }
+            envp.offset(1) as *const usize
+        }
+    }
 }
 
 // This is the entry point of all programs or, IOW, this is where all programs
@@ -198,7 +211,10 @@ pub extern "C" fn start(sp: &'static Stack) -> ! {
         fn main(argc: isize, argv: *const *const u8) -> isize;
     }
 
-    unsafe { ::linux::exit_group(main(sp.argc(), sp.argv()) as i32) }
+    unsafe {
+        ::sys::os::init(sp.auxv());
+        ::linux::exit_group(main(sp.argc(), sp.argv()) as i32)
+    }
 }
 
 // This is how the `start` lang item actually works:
@@ -0,0 +1,67 @@
+use cmp;
+use ctypes::c_int;
+use io;
+use linux::{self, epoll_event};
+use sys::fd::FileDesc;
+use sys::FromInner;
+use time::Duration;
+use super::cvt;
+
+pub struct Epoll(FileDesc);
+
+impl Epoll {
+    pub fn new() -> io::Result<Epoll> {
+        let fd = cvt(unsafe { linux::epoll_create1(linux::EPOLL_CLOEXEC) })?;
+        Ok(Epoll(FileDesc::new(fd as c_int)))
+    }
+
+    pub fn ctl(&self, op: c_int, fd: c_int, events: u32, data: u64)
+               -> io::Result<()> {
+        let mut event = epoll_event { events: events, data: data };
+        cvt(unsafe { linux::epoll_ctl(self.0.raw(), op, fd, &mut event) })?;
+        Ok(())
+    }
+
+    /// Replaces the contents of `events` with the ready events, waiting for
+    /// at most `timeout`. Only the capacity of `events` is used.
+    pub fn wait(&self,
+                events: &mut Vec<epoll_event>,
+                timeout: Option<Duration>)
+                -> io::Result<usize> {
+        events.clear();
+        let max = cmp::min(events.capacity(), c_int::max_value() as usize);
+        let n = cvt(unsafe {
+            linux::epoll_pwait(self.0.raw(),
+                               events.as_mut_ptr(),
+                               max as c_int,
+                               timeout_ms(timeout))
+        })?;
+        unsafe { events.set_len(n); }
+        Ok(n)
+    }
+
+    pub fn fd(&self) -> &FileDesc { &self.0 }
+
+    pub fn into_fd(self) -> FileDesc { self.0 }
+}
+
+impl FromInner<c_int> for Epoll {
+    fn from_inner(fd: c_int) -> Epoll {
+        Epoll(FileDesc::new(fd))
+    }
+}
+
+// Rounds up to whole milliseconds, so that a short timeout doesn't turn into
+// a busy loop. `None` waits forever.
+fn timeout_ms(timeout: Option<Duration>) -> c_int {
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return -1,
+    };
+    let nanos = timeout.subsec_nanos() as u64;
+    let millis = timeout.as_secs()
+        .checked_mul(1000)
+        .and_then(|ms| ms.checked_add((nanos + 999_999) / 1_000_000))
+        .unwrap_or(u64::max_value());
+    cmp::min(millis, c_int::max_value() as u64) as c_int
+}
@@ -0,0 +1,55 @@
+use ctypes::c_int;
+use io;
+use linux;
+use mem;
+use slice;
+use sys::fd::FileDesc;
+use sys::FromInner;
+use super::cvt;
+
+pub struct EventFd(FileDesc);
+
+impl EventFd {
+    pub fn new(initval: u32, semaphore: bool) -> io::Result<EventFd> {
+        let mut flags = linux::EFD_CLOEXEC;
+        if semaphore {
+            flags |= linux::EFD_SEMAPHORE;
+        }
+        let fd = cvt(unsafe { linux::eventfd2(initval, flags) })?;
+        Ok(EventFd(FileDesc::new(fd as c_int)))
+    }
+
+    pub fn read(&self) -> io::Result<u64> {
+        let mut value = 0u64;
+        let buf = unsafe {
+            slice::from_raw_parts_mut(&mut value as *mut u64 as *mut u8,
+                                      mem::size_of::<u64>())
+        };
+        // An eventfd always transfers all 8 bytes or fails
+        self.0.read(buf)?;
+        Ok(value)
+    }
+
+    pub fn write(&self, value: u64) -> io::Result<()> {
+        let buf = unsafe {
+            slice::from_raw_parts(&value as *const u64 as *const u8,
+                                  mem::size_of::<u64>())
+        };
+        self.0.write(buf)?;
+        Ok(())
+    }
+
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.0.set_nonblocking(nonblocking)
+    }
+
+    pub fn fd(&self) -> &FileDesc { &self.0 }
+
+    pub fn into_fd(self) -> FileDesc { self.0 }
+}
+
+impl FromInner<c_int> for EventFd {
+    fn from_inner(fd: c_int) -> EventFd {
+        EventFd(FileDesc::new(fd))
+    }
+}
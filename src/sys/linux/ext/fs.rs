@@ -13,10 +13,11 @@
 #![stable(feature = "rust1", since = "1.0.0")]
 
 use fs::{self, Permissions, OpenOptions};
-use io;
+use io::{self, IoSlice, IoSliceMut};
 use libc;
 use path::Path;
 use sys;
+use sys::ext::io::AsRawFd;
 use sys_common::{FromInner, AsInner, AsInnerMut};
 use sys::platform::fs::MetadataExt as UnixMetadataExt;
 
@@ -53,6 +54,22 @@ pub trait FileExt {
     /// short write.
     #[unstable(feature = "file_offset", issue = "35918")]
     fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize>;
+
+    /// Like `read_at`, except that it reads into a slice of buffers.
+    ///
+    /// Data is copied to fill each buffer in order, starting at `offset`,
+    /// with a single `preadv` call. The current file cursor is not affected.
+    #[unstable(feature = "iovec", issue = "0")]
+    fn read_vectored_at(&self, bufs: &mut [IoSliceMut], offset: u64)
+                        -> io::Result<usize>;
+
+    /// Like `write_at`, except that it writes from a slice of buffers.
+    ///
+    /// Data is copied from each buffer in order, starting at `offset`, with
+    /// a single `pwritev` call. The current file cursor is not affected.
+    #[unstable(feature = "iovec", issue = "0")]
+    fn write_vectored_at(&self, bufs: &[IoSlice], offset: u64)
+                         -> io::Result<usize>;
 }
 
 #[unstable(feature = "file_offset", issue = "35918")]
@@ -63,6 +80,14 @@ impl FileExt for fs::File {
     fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
         self.as_inner().write_at(buf, offset)
     }
+    fn read_vectored_at(&self, bufs: &mut [IoSliceMut], offset: u64)
+                        -> io::Result<usize> {
+        self.as_inner().read_vectored_at(bufs, offset)
+    }
+    fn write_vectored_at(&self, bufs: &[IoSlice], offset: u64)
+                         -> io::Result<usize> {
+        self.as_inner().write_vectored_at(bufs, offset)
+    }
 }
 
 /// Unix-specific extensions to `Permissions`
@@ -345,6 +370,47 @@ pub fn symlink<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dst: Q) -> io::Result<()>
     sys::fs::symlink(src.as_ref(), dst.as_ref())
 }
 
+/// Changes the owner and the group of the file at `path`.
+///
+/// IDs given as `None` are left unchanged. Symbolic links are followed, see
+/// `lchown` to change the owner of the link itself.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(unix_chown)]
+/// use std::os::unix::fs;
+///
+/// # fn foo() -> std::io::Result<()> {
+/// try!(fs::chown("/sandbox", Some(0), Some(0)));
+/// # Ok(())
+/// # }
+/// ```
+#[unstable(feature = "unix_chown", issue = "0")]
+pub fn chown<P: AsRef<Path>>(path: P, uid: Option<u32>, gid: Option<u32>)
+                             -> io::Result<()> {
+    sys::fs::chown(path.as_ref(), uid, gid, true)
+}
+
+/// Changes the owner and the group of the file referred to by `fd`.
+///
+/// IDs given as `None` are left unchanged.
+#[unstable(feature = "unix_chown", issue = "0")]
+pub fn fchown<F: AsRawFd>(fd: &F, uid: Option<u32>, gid: Option<u32>)
+                          -> io::Result<()> {
+    sys::fs::fchown(fd.as_raw_fd(), uid, gid)
+}
+
+/// Changes the owner and the group of the file at `path`, without following
+/// symbolic links.
+///
+/// IDs given as `None` are left unchanged.
+#[unstable(feature = "unix_chown", issue = "0")]
+pub fn lchown<P: AsRef<Path>>(path: P, uid: Option<u32>, gid: Option<u32>)
+                              -> io::Result<()> {
+    sys::fs::chown(path.as_ref(), uid, gid, false)
+}
+
 #[stable(feature = "dir_builder", since = "1.6.0")]
 /// An extension trait for `fs::DirBuilder` for unix-specific options.
 pub trait DirBuilderExt {
@@ -1,9 +1,15 @@
 #![stable(feature = "steed", since = "1.0.0")]
 
+use libc;
+
+use fmt;
 use fs;
+use io;
 use os::raw;
+use slice;
 use sys;
 use sys_common::{AsInner, FromInner, IntoInner};
+use time::Duration;
 
 #[stable(feature = "steed", since = "1.0.0")]
 pub type RawFd = raw::c_int;
@@ -44,3 +50,170 @@ impl IntoRawFd for fs::File {
         self.into_inner().into_fd().into_raw()
     }
 }
+
+#[stable(feature = "asraw_stdio", since = "1.21.0")]
+impl AsRawFd for io::Stdin {
+    fn as_raw_fd(&self) -> RawFd { 0 }
+}
+
+#[stable(feature = "asraw_stdio", since = "1.21.0")]
+impl AsRawFd for io::Stdout {
+    fn as_raw_fd(&self) -> RawFd { 1 }
+}
+
+#[stable(feature = "asraw_stdio", since = "1.21.0")]
+impl AsRawFd for io::Stderr {
+    fn as_raw_fd(&self) -> RawFd { 2 }
+}
+
+/// Interest and readiness: there is data to read.
+#[unstable(feature = "poll", issue = "0")]
+pub const POLLIN: i16 = libc::POLLIN;
+/// Interest and readiness: there is urgent data to read, e.g. out-of-band
+/// data on a TCP socket.
+#[unstable(feature = "poll", issue = "0")]
+pub const POLLPRI: i16 = libc::POLLPRI;
+/// Interest and readiness: writing won't block.
+#[unstable(feature = "poll", issue = "0")]
+pub const POLLOUT: i16 = libc::POLLOUT;
+/// Readiness: an error is pending on the descriptor. Always reported, no
+/// need to ask for it.
+#[unstable(feature = "poll", issue = "0")]
+pub const POLLERR: i16 = libc::POLLERR;
+/// Readiness: the descriptor was hung up, e.g. the write end of a pipe was
+/// closed. Always reported, no need to ask for it.
+#[unstable(feature = "poll", issue = "0")]
+pub const POLLHUP: i16 = libc::POLLHUP;
+/// Readiness: the descriptor isn't open. Always reported, no need to ask
+/// for it.
+#[unstable(feature = "poll", issue = "0")]
+pub const POLLNVAL: i16 = libc::POLLNVAL;
+
+/// A file descriptor to wait on with `poll`.
+///
+/// It has the same layout as `struct pollfd`.
+#[unstable(feature = "poll", issue = "0")]
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct PollFd(libc::pollfd);
+
+impl PollFd {
+    /// Creates an entry waiting for the events in `events`, a bitwise OR of
+    /// `POLLIN`, `POLLPRI` and `POLLOUT`.
+    ///
+    /// A negative `fd` makes `poll` skip the entry.
+    #[unstable(feature = "poll", issue = "0")]
+    pub fn new(fd: RawFd, events: i16) -> PollFd {
+        PollFd(libc::pollfd { fd: fd, events: events, revents: 0 })
+    }
+
+    /// Returns the file descriptor.
+    #[unstable(feature = "poll", issue = "0")]
+    pub fn fd(&self) -> RawFd {
+        self.0.fd
+    }
+
+    /// Returns the events waited for.
+    #[unstable(feature = "poll", issue = "0")]
+    pub fn events(&self) -> i16 {
+        self.0.events
+    }
+
+    /// Returns the events that occurred during the last `poll`, a bitwise
+    /// OR of the `POLL*` constants.
+    #[unstable(feature = "poll", issue = "0")]
+    pub fn revents(&self) -> i16 {
+        self.0.revents
+    }
+}
+
+#[unstable(feature = "poll", issue = "0")]
+impl fmt::Debug for PollFd {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PollFd")
+            .field("fd", &self.0.fd)
+            .field("events", &self.0.events)
+            .field("revents", &self.0.revents)
+            .finish()
+    }
+}
+
+/// Waits until one of `fds` is ready or `timeout` has passed, and records
+/// what occurred in the `revents` of each entry.
+///
+/// A timeout of `None` waits forever. If a signal handler runs while
+/// waiting, the wait is resumed for the rest of the timeout.
+///
+/// Returns the number of entries with nonzero `revents`, 0 if the timeout
+/// expired.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(poll)]
+/// use std::io;
+/// use std::os::unix::io::{poll, AsRawFd, PollFd, POLLIN};
+/// use std::time::Duration;
+///
+/// # fn foo() -> io::Result<()> {
+/// let mut fds = [PollFd::new(io::stdin().as_raw_fd(), POLLIN)];
+/// if try!(poll(&mut fds, Some(Duration::from_secs(5)))) == 0 {
+///     println!("no input within 5 seconds");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[unstable(feature = "poll", issue = "0")]
+pub fn poll(fds: &mut [PollFd], timeout: Option<Duration>) -> io::Result<usize> {
+    let fds = unsafe {
+        slice::from_raw_parts_mut(fds.as_mut_ptr() as *mut libc::pollfd,
+                                  fds.len())
+    };
+    sys::io::poll(fds, timeout)
+}
+
+/// Returns whether `fd` refers to a terminal.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(is_terminal)]
+/// use std::io;
+/// use std::os::unix::io::is_terminal;
+///
+/// if !is_terminal(&io::stdin()) {
+///     println!("not reading from a terminal, prompts are disabled");
+/// }
+/// ```
+#[unstable(feature = "is_terminal", issue = "0")]
+pub fn is_terminal<F: AsRawFd + ?Sized>(fd: &F) -> bool {
+    sys::termios::is_terminal(fd.as_raw_fd())
+}
+
+/// Types that may refer to a terminal.
+#[unstable(feature = "is_terminal", issue = "0")]
+pub trait IsTerminal {
+    /// Returns whether `self` refers to a terminal.
+    #[unstable(feature = "is_terminal", issue = "0")]
+    fn is_terminal(&self) -> bool;
+}
+
+#[unstable(feature = "is_terminal", issue = "0")]
+impl IsTerminal for fs::File {
+    fn is_terminal(&self) -> bool { is_terminal(self) }
+}
+
+#[unstable(feature = "is_terminal", issue = "0")]
+impl IsTerminal for io::Stdin {
+    fn is_terminal(&self) -> bool { is_terminal(self) }
+}
+
+#[unstable(feature = "is_terminal", issue = "0")]
+impl IsTerminal for io::Stdout {
+    fn is_terminal(&self) -> bool { is_terminal(self) }
+}
+
+#[unstable(feature = "is_terminal", issue = "0")]
+impl IsTerminal for io::Stderr {
+    fn is_terminal(&self) -> bool { is_terminal(self) }
+}
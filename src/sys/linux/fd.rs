@@ -10,8 +10,9 @@
 
 #![unstable(reason = "not public", issue = "0", feature = "fd")]
 
-use ctypes::{c_int, c_char};
-use io::{self, Read};
+use ctypes::{c_int, c_char, c_ulong};
+use cmp;
+use io::{self, IoSlice, IoSliceMut, Read};
 use linux;
 use mem;
 use sync::atomic::{AtomicBool, Ordering};
@@ -43,6 +44,14 @@ impl FileDesc {
         })?)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        unsafe {
+            cvt(linux::readv(self.fd,
+                             bufs.as_ptr() as *const linux::iovec,
+                             iovcnt(bufs.len())))
+        }
+    }
+
     pub fn read_to_end(&self, buf: &mut Vec<u8>) -> io::Result<usize> {
         let mut me = self;
         (&mut me).read_to_end(buf)
@@ -57,12 +66,30 @@ impl FileDesc {
         }
     }
 
+    pub fn read_vectored_at(&self, bufs: &mut [IoSliceMut], offset: u64)
+                            -> io::Result<usize> {
+        unsafe {
+            cvt(linux::preadv(self.fd,
+                              bufs.as_ptr() as *const linux::iovec,
+                              iovcnt(bufs.len()),
+                              offset as i64))
+        }
+    }
+
     pub fn write(&self, buf: &[u8]) -> io::Result<usize> {
         Ok(cvt(unsafe {
             linux::write(self.fd, buf.as_ptr() as *const c_char, buf.len())
         })?)
     }
 
+    pub fn write_vectored(&self, bufs: &[IoSlice]) -> io::Result<usize> {
+        unsafe {
+            cvt(linux::writev(self.fd,
+                              bufs.as_ptr() as *const linux::iovec,
+                              iovcnt(bufs.len())))
+        }
+    }
+
     pub fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
         unsafe {
             cvt(linux::pwrite64(self.fd,
@@ -72,6 +99,16 @@ impl FileDesc {
         }
     }
 
+    pub fn write_vectored_at(&self, bufs: &[IoSlice], offset: u64)
+                             -> io::Result<usize> {
+        unsafe {
+            cvt(linux::pwritev(self.fd,
+                               bufs.as_ptr() as *const linux::iovec,
+                               iovcnt(bufs.len()),
+                               offset as i64))
+        }
+    }
+
     pub fn set_cloexec(&self) -> io::Result<()> {
         unsafe {
             cvt(linux::ioctl(self.fd, linux::FIOCLEX, 0))?;
@@ -79,11 +116,15 @@ impl FileDesc {
         Ok(())
     }
 
-    /*
     pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
-        unimplemented!();
+        let v = nonblocking as c_int;
+        unsafe {
+            cvt(linux::ioctl(self.fd,
+                             linux::FIONBIO,
+                             &v as *const c_int as c_ulong))?;
+        }
+        Ok(())
     }
-    */
 
     pub fn duplicate(&self) -> io::Result<FileDesc> {
         // We want to atomically duplicate this file descriptor and set the
@@ -126,11 +167,22 @@ impl FileDesc {
     }
 }
 
+// `readv` and friends fail with `EINVAL` when given more than `UIO_MAXIOV`
+// buffers, so only pass that many and let the caller see a short read or
+// write.
+pub fn iovcnt(len: usize) -> c_int {
+    cmp::min(len, linux::UIO_MAXIOV) as c_int
+}
+
 impl<'a> Read for &'a FileDesc {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         (**self).read(buf)
     }
 
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        (**self).read_vectored(bufs)
+    }
+
     fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
         unsafe { read_to_end_uninitialized(self, buf) }
     }
@@ -1,18 +1,23 @@
 use os::unix::prelude::*;
 
-use ctypes::c_int;
-use ctypes::c_ushort;
+use cmp;
+use ctypes::{c_int, c_short, c_uint, c_ulong, c_ushort};
 use ffi::{CString, CStr, OsString, OsStr};
 use fmt;
-use io::{self, Error, ErrorKind, SeekFrom};
+use fs;
+use io::{self, Error, ErrorKind, IoSlice, IoSliceMut, Read, SeekFrom, Write};
 use linux::types::{mode_t, stat64};
 use linux;
 use mem;
-use path::{Path, PathBuf};
+use path::{Component, Path, PathBuf};
+use ptr;
 use super::{cvt, cvt_r};
+use sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use sys::errno;
 use sys::ext::ffi::OsStrExt;
 use sys::fd::FileDesc;
+use sys::io::DEFAULT_BUF_SIZE;
+use sys::os;
 use sys::time::SystemTime;
 use sys::{AsInner, FromInner};
 
@@ -85,7 +90,44 @@ pub struct FileType { mode: mode_t }
 
 pub struct DirBuilder { mode: mode_t }
 
+#[derive(Copy, Clone)]
+pub struct FileTimes([linux::timespec; 2]);
+
+pub struct Dir {
+    fd: FileDesc,
+    root: PathBuf,
+}
+
 impl FileAttr {
+    /// Converts the result of `statx` to what `fstatat64` would have
+    /// returned.
+    pub fn from_statx(x: &linux::statx) -> FileAttr {
+        // The encoding of `new_encode_dev` in include/linux/kdev_t.h
+        fn dev(major: u32, minor: u32) -> u64 {
+            let (major, minor) = (major as u64, minor as u64);
+            (minor & 0xff) | (major << 8) | ((minor & !0xff) << 12)
+        }
+
+        let mut stat: stat64 = unsafe { mem::zeroed() };
+        stat.st_dev = dev(x.stx_dev_major, x.stx_dev_minor) as _;
+        stat.st_ino = x.stx_ino as _;
+        stat.st_mode = x.stx_mode as _;
+        stat.st_nlink = x.stx_nlink as _;
+        stat.st_uid = x.stx_uid as _;
+        stat.st_gid = x.stx_gid as _;
+        stat.st_rdev = dev(x.stx_rdev_major, x.stx_rdev_minor) as _;
+        stat.st_size = x.stx_size as _;
+        stat.st_blksize = x.stx_blksize as _;
+        stat.st_blocks = x.stx_blocks as _;
+        stat.st_atime = x.stx_atime.tv_sec as _;
+        stat.st_atime_nsec = x.stx_atime.tv_nsec as _;
+        stat.st_mtime = x.stx_mtime.tv_sec as _;
+        stat.st_mtime_nsec = x.stx_mtime.tv_nsec as _;
+        stat.st_ctime = x.stx_ctime.tv_sec as _;
+        stat.st_ctime_nsec = x.stx_ctime.tv_nsec as _;
+        FileAttr { stat: stat }
+    }
+
     pub fn size(&self) -> u64 { self.stat.st_size as u64 }
     pub fn perm(&self) -> FilePermissions {
         FilePermissions { mode: self.stat.st_mode & 0o777 }
@@ -136,6 +178,40 @@ impl FileType {
     pub fn is(&self, mode: mode_t) -> bool { self.mode & linux::S_IFMT == mode }
 }
 
+impl FileTimes {
+    pub fn new() -> FileTimes {
+        let omit = linux::timespec { tv_sec: 0, tv_nsec: linux::UTIME_OMIT };
+        FileTimes([omit, omit])
+    }
+
+    pub fn set_accessed(&mut self, t: SystemTime) { self.0[0] = *t.as_inner(); }
+    pub fn set_modified(&mut self, t: SystemTime) { self.0[1] = *t.as_inner(); }
+
+    pub fn set_accessed_now(&mut self) { self.0[0] = FileTimes::now(); }
+    pub fn set_modified_now(&mut self) { self.0[1] = FileTimes::now(); }
+
+    // Lets the kernel read the clock, so that the timestamp matches the
+    // granularity of the filesystem like the ones set by a `write` would.
+    fn now() -> linux::timespec {
+        linux::timespec { tv_sec: 0, tv_nsec: linux::UTIME_NOW }
+    }
+}
+
+impl fmt::Debug for FileTimes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn field(t: &linux::timespec) -> Option<(i64, i64)> {
+            match t.tv_nsec {
+                linux::UTIME_OMIT => None,
+                _ => Some((t.tv_sec as i64, t.tv_nsec as i64)),
+            }
+        }
+        f.debug_struct("FileTimes")
+         .field("accessed", &field(&self.0[0]))
+         .field("modified", &field(&self.0[1]))
+         .finish()
+    }
+}
+
 impl FromInner<u32> for FilePermissions {
     fn from_inner(mode: u32) -> FilePermissions {
         FilePermissions { mode: mode as mode_t }
@@ -187,6 +263,11 @@ impl Iterator for ReadDir {
                     as *const u8 as *const linux::linux_dirent64;
                 read_dir.offset += (*dent).d_reclen as usize;
 
+                let name = CStr::from_ptr((*dent).d_name.as_ptr()).to_bytes();
+                if name == b"." || name == b".." {
+                    continue;
+                }
+
                 return Some(Ok(DirEntry::from_raw(dent, read_dir.root.clone())));
             }
         }
@@ -209,18 +290,36 @@ impl DirEntry {
     }
 
     pub fn file_type(&self) -> io::Result<FileType> {
-        match self.entry.d_type {
-            linux::DT_CHR => Ok(FileType { mode: linux::S_IFCHR }),
-            linux::DT_FIFO => Ok(FileType { mode: linux::S_IFIFO }),
-            linux::DT_LNK => Ok(FileType { mode: linux::S_IFLNK }),
-            linux::DT_REG => Ok(FileType { mode: linux::S_IFREG }),
-            linux::DT_SOCK => Ok(FileType { mode: linux::S_IFSOCK }),
-            linux::DT_DIR => Ok(FileType { mode: linux::S_IFDIR }),
-            linux::DT_BLK => Ok(FileType { mode: linux::S_IFBLK }),
-            _ => lstat(&self.path()).map(|m| m.file_type()),
+        match self.d_type() {
+            Some(ft) => Ok(ft),
+            None => lstat(&self.path()).map(|m| m.file_type()),
         }
     }
 
+    /// Like `file_type`, but if the filesystem doesn't fill in `d_type` this
+    /// stats the entry relative to `dir`, the directory it was read from,
+    /// instead of by its full path.
+    pub fn file_type_at(&self, dir: &Dir) -> io::Result<FileType> {
+        match self.d_type() {
+            Some(ft) => Ok(ft),
+            None => dir.lstat_at(Path::new(&self.name)).map(|m| m.file_type()),
+        }
+    }
+
+    fn d_type(&self) -> Option<FileType> {
+        let mode = match self.entry.d_type {
+            linux::DT_CHR => linux::S_IFCHR,
+            linux::DT_FIFO => linux::S_IFIFO,
+            linux::DT_LNK => linux::S_IFLNK,
+            linux::DT_REG => linux::S_IFREG,
+            linux::DT_SOCK => linux::S_IFSOCK,
+            linux::DT_DIR => linux::S_IFDIR,
+            linux::DT_BLK => linux::S_IFBLK,
+            _ => return None,
+        };
+        Some(FileType { mode: mode })
+    }
+
     pub fn ino(&self) -> u64 {
         self.entry.d_ino as u64
     }
@@ -252,6 +351,18 @@ impl OpenOptions {
     pub fn custom_flags(&mut self, flags: i32) { self.custom_flags = flags; }
     pub fn mode(&mut self, mode: u32) { self.mode = mode as mode_t; }
 
+    /// The flags and mode `openat` is called with, for other ways of opening
+    /// files like io_uring.
+    pub fn open_flags(&self) -> io::Result<c_int> {
+        Ok(linux::O_CLOEXEC |
+           linux::O_LARGEFILE |
+           self.get_access_mode()? |
+           self.get_creation_mode()? |
+           (self.custom_flags as c_int & !linux::O_ACCMODE))
+    }
+
+    pub fn open_mode(&self) -> c_ushort { self.mode as c_ushort }
+
     fn get_access_mode(&self) -> io::Result<c_int> {
         match (self.read, self.write, self.append) {
             (true,  false, false) => Ok(linux::O_RDONLY),
@@ -295,13 +406,15 @@ impl File {
     }
 
     pub fn open_c(path: &CStr, opts: &OpenOptions) -> io::Result<File> {
-        let flags = linux::O_CLOEXEC |
-                    linux::O_LARGEFILE |
-                    opts.get_access_mode()? |
-                    opts.get_creation_mode()? |
-                    (opts.custom_flags as c_int & !linux::O_ACCMODE);
+        File::open_at_c(linux::AT_FDCWD, path, opts)
+    }
+
+    fn open_at_c(dirfd: c_int, path: &CStr, opts: &OpenOptions)
+        -> io::Result<File>
+    {
+        let flags = opts.open_flags()?;
         let fd = FileDesc::new(cvt_r(|| unsafe {
-            linux::open(path.as_ptr(), flags, opts.mode as c_ushort)
+            linux::openat(dirfd, path.as_ptr(), flags, opts.open_mode())
         })? as c_int);
 
         // Currently the standard library supports Linux 2.6.18 which did not
@@ -342,10 +455,109 @@ impl File {
         }).map(|_| ())
     }
 
+    pub fn set_times(&self, times: FileTimes) -> io::Result<()> {
+        // With a null path `utimensat` operates on the descriptor itself,
+        // which is how `futimens` is implemented.
+        cvt(unsafe {
+            linux::utimensat(self.0.raw(), ptr::null(), times.0.as_ptr(), 0)
+        })?;
+        Ok(())
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.flock(linux::LOCK_SH).map(|_| ())
+    }
+
+    pub fn lock_exclusive(&self) -> io::Result<()> {
+        self.flock(linux::LOCK_EX).map(|_| ())
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<bool> {
+        self.flock(linux::LOCK_SH | linux::LOCK_NB)
+    }
+
+    pub fn try_lock_exclusive(&self) -> io::Result<bool> {
+        self.flock(linux::LOCK_EX | linux::LOCK_NB)
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        self.flock(linux::LOCK_UN).map(|_| ())
+    }
+
+    // Returns whether the lock was taken, which can only be `false` when
+    // `LOCK_NB` is part of `op`.
+    fn flock(&self, op: c_uint) -> io::Result<bool> {
+        match cvt_r(|| unsafe { linux::flock(self.0.raw() as c_uint, op) }) {
+            Ok(_) => Ok(true),
+            Err(ref e) if e.raw_os_error() == Some(errno::EWOULDBLOCK) => {
+                Ok(false)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn lock_range(&self, exclusive: bool, start: u64, len: u64, wait: bool)
+        -> io::Result<bool>
+    {
+        let kind = if exclusive { linux::F_WRLCK } else { linux::F_RDLCK };
+        self.ofd_lock(kind, start, len, wait)
+    }
+
+    pub fn unlock_range(&self, start: u64, len: u64) -> io::Result<()> {
+        self.ofd_lock(linux::F_UNLCK, start, len, false).map(|_| ())
+    }
+
+    pub fn add_seals(&self, seals: u32) -> io::Result<()> {
+        cvt(unsafe {
+            linux::fcntl(self.0.raw(), linux::F_ADD_SEALS, seals as c_ulong)
+        })?;
+        Ok(())
+    }
+
+    pub fn seals(&self) -> io::Result<u32> {
+        let seals = cvt(unsafe {
+            linux::fcntl(self.0.raw(), linux::F_GET_SEALS, 0)
+        })?;
+        Ok(seals as u32)
+    }
+
+    // Open file description locks are owned by the file description instead
+    // of by the process, so unlike classic POSIX record locks they aren't
+    // released when the process closes an unrelated descriptor of the same
+    // file, and threads sharing a `File` see them consistently.
+    fn ofd_lock(&self, kind: c_short, start: u64, len: u64, wait: bool)
+        -> io::Result<bool>
+    {
+        let mut lock = linux::flock64 {
+            l_type: kind,
+            l_whence: linux::SEEK_SET as c_short,
+            l_start: start as i64,
+            l_len: len as i64,
+            // Must be zero for open file description locks.
+            l_pid: 0,
+        };
+        let cmd = if wait { linux::F_OFD_SETLKW } else { linux::F_OFD_SETLK };
+        match cvt_r(|| unsafe {
+            linux::fcntl(self.0.raw(), cmd, &mut lock as *mut _ as c_ulong)
+        }) {
+            Ok(_) => Ok(true),
+            Err(ref e) if !wait &&
+                          (e.raw_os_error() == Some(errno::EAGAIN) ||
+                           e.raw_os_error() == Some(errno::EACCES)) => {
+                Ok(false)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     pub fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.0.read(buf)
     }
 
+    pub fn read_vectored(&self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        self.0.read_vectored(bufs)
+    }
+
     pub fn read_to_end(&self, buf: &mut Vec<u8>) -> io::Result<usize> {
         self.0.read_to_end(buf)
     }
@@ -354,14 +566,28 @@ impl File {
         self.0.read_at(buf, offset)
     }
 
+    pub fn read_vectored_at(&self, bufs: &mut [IoSliceMut], offset: u64)
+                            -> io::Result<usize> {
+        self.0.read_vectored_at(bufs, offset)
+    }
+
     pub fn write(&self, buf: &[u8]) -> io::Result<usize> {
         self.0.write(buf)
     }
 
+    pub fn write_vectored(&self, bufs: &[IoSlice]) -> io::Result<usize> {
+        self.0.write_vectored(bufs)
+    }
+
     pub fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
         self.0.write_at(buf, offset)
     }
 
+    pub fn write_vectored_at(&self, bufs: &[IoSlice], offset: u64)
+                             -> io::Result<usize> {
+        self.0.write_vectored_at(bufs, offset)
+    }
+
     pub fn flush(&self) -> io::Result<()> {
         Ok(())
     }
@@ -374,11 +600,51 @@ impl File {
             SeekFrom::End(off) => (linux::SEEK_END, off),
             SeekFrom::Current(off) => (linux::SEEK_CUR, off),
         };
+        self.lseek(pos, whence)
+    }
+
+    pub fn seek_data(&self, from: u64) -> io::Result<Option<u64>> {
+        match self.lseek(from as i64, linux::SEEK_DATA) {
+            Ok(n) => Ok(Some(n)),
+            // There's no more data past `from`.
+            Err(ref e) if e.raw_os_error() == Some(errno::ENXIO) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn seek_hole(&self, from: u64) -> io::Result<u64> {
+        // The end of the file counts as a hole, so this always finds one.
+        self.lseek(from as i64, linux::SEEK_HOLE)
+    }
+
+    fn lseek(&self, pos: i64, whence: c_uint) -> io::Result<u64> {
         let mut n = 0;
         cvt(unsafe { linux::_llseek(self.0.raw(), pos, &mut n, whence) })?;
         Ok(n as u64)
     }
 
+    pub fn allocate(&self, offset: u64, len: u64) -> io::Result<()> {
+        self.fallocate(0, offset, len)
+    }
+
+    pub fn punch_hole(&self, offset: u64, len: u64) -> io::Result<()> {
+        // The kernel only accepts `PUNCH_HOLE` together with `KEEP_SIZE`.
+        self.fallocate(linux::FALLOC_FL_PUNCH_HOLE | linux::FALLOC_FL_KEEP_SIZE,
+                       offset,
+                       len)
+    }
+
+    pub fn zero_range(&self, offset: u64, len: u64) -> io::Result<()> {
+        self.fallocate(linux::FALLOC_FL_ZERO_RANGE, offset, len)
+    }
+
+    fn fallocate(&self, mode: c_int, offset: u64, len: u64) -> io::Result<()> {
+        cvt_r(|| unsafe {
+            linux::fallocate(self.0.raw(), mode, offset as i64, len as i64)
+        })?;
+        Ok(())
+    }
+
     pub fn duplicate(&self) -> io::Result<File> {
         self.0.duplicate().map(File)
     }
@@ -404,6 +670,318 @@ impl DirBuilder {
     }
 }
 
+impl Dir {
+    pub fn open(p: &Path) -> io::Result<Dir> {
+        let path = cstr(p)?;
+        Dir::open_at_c(linux::AT_FDCWD, &path, 0, p.into())
+    }
+
+    fn open_nofollow(p: &Path) -> io::Result<Dir> {
+        let path = cstr(p)?;
+        Dir::open_at_c(linux::AT_FDCWD, &path, linux::O_NOFOLLOW, p.into())
+    }
+
+    fn open_at_c(dirfd: c_int, path: &CStr, flags: c_int, root: PathBuf)
+        -> io::Result<Dir>
+    {
+        let flags = linux::O_CLOEXEC |
+                    linux::O_LARGEFILE |
+                    linux::O_RDONLY |
+                    linux::O_DIRECTORY |
+                    flags;
+        let fd = FileDesc::new(cvt_r(|| unsafe {
+            linux::openat(dirfd, path.as_ptr(), flags, 0)
+        })? as c_int);
+        fd.set_cloexec()?;
+        Ok(Dir { fd: fd, root: root })
+    }
+
+    pub fn open_dir_at(&self, p: &Path) -> io::Result<Dir> {
+        self.open_subdir(p, false)
+    }
+
+    pub fn open_subdir(&self, p: &Path, follow: bool) -> io::Result<Dir> {
+        let path = cstr(p)?;
+        let flags = if follow { 0 } else { linux::O_NOFOLLOW };
+        Dir::open_at_c(self.fd.raw(), &path, flags, self.root.join(p))
+    }
+
+    pub fn file_attr(&self) -> io::Result<FileAttr> {
+        let mut stat: stat64 = unsafe { mem::zeroed() };
+        cvt(unsafe { linux::fstat64(self.fd.raw(), &mut stat) })?;
+        Ok(FileAttr { stat: stat })
+    }
+
+    pub fn open_at(&self, p: &Path, opts: &OpenOptions) -> io::Result<File> {
+        let path = cstr(p)?;
+        File::open_at_c(self.fd.raw(), &path, opts)
+    }
+
+    pub fn stat_at(&self, p: &Path) -> io::Result<FileAttr> {
+        self.fstatat(p, 0)
+    }
+
+    pub fn lstat_at(&self, p: &Path) -> io::Result<FileAttr> {
+        self.fstatat(p, linux::AT_SYMLINK_NOFOLLOW)
+    }
+
+    fn fstatat(&self, p: &Path, flag: c_int) -> io::Result<FileAttr> {
+        let p = cstr(p)?;
+        let mut stat: stat64 = unsafe { mem::zeroed() };
+        cvt(unsafe {
+            linux::fstatat64(self.fd.raw(), p.as_ptr(), &mut stat, flag)
+        })?;
+        Ok(FileAttr { stat: stat })
+    }
+
+    pub fn unlink_at(&self, p: &Path) -> io::Result<()> {
+        let p = cstr(p)?;
+        cvt(unsafe { linux::unlinkat(self.fd.raw(), p.as_ptr(), 0) })?;
+        Ok(())
+    }
+
+    pub fn rmdir_at(&self, p: &Path) -> io::Result<()> {
+        let p = cstr(p)?;
+        cvt(unsafe {
+            linux::unlinkat(self.fd.raw(), p.as_ptr(), linux::AT_REMOVEDIR)
+        })?;
+        Ok(())
+    }
+
+    pub fn mkdir_at(&self, p: &Path, mode: u32) -> io::Result<()> {
+        let p = cstr(p)?;
+        cvt(unsafe {
+            linux::mkdirat(self.fd.raw(), p.as_ptr(), mode as c_ushort)
+        })?;
+        Ok(())
+    }
+
+    pub fn rename_at(&self, old: &Path, new_dir: &Dir, new: &Path, flags: u32)
+        -> io::Result<()>
+    {
+        let old = cstr(old)?;
+        let new = cstr(new)?;
+        // `renameat2` only exists since Linux 3.15, so don't require it
+        // unless the caller actually asked for one of its flags.
+        cvt(unsafe {
+            if flags == 0 {
+                linux::renameat(self.fd.raw(), old.as_ptr(),
+                                new_dir.fd.raw(), new.as_ptr())
+            } else {
+                linux::renameat2(self.fd.raw(), old.as_ptr(),
+                                 new_dir.fd.raw(), new.as_ptr(),
+                                 flags)
+            }
+        })?;
+        Ok(())
+    }
+
+    pub fn link_at(&self, old: &Path, new_dir: &Dir, new: &Path)
+        -> io::Result<()>
+    {
+        let old = cstr(old)?;
+        let new = cstr(new)?;
+        cvt(unsafe {
+            linux::linkat(self.fd.raw(), old.as_ptr(),
+                          new_dir.fd.raw(), new.as_ptr(),
+                          0)
+        })?;
+        Ok(())
+    }
+
+    pub fn symlink_at(&self, src: &Path, dst: &Path) -> io::Result<()> {
+        let src = cstr(src)?;
+        let dst = cstr(dst)?;
+        cvt(unsafe {
+            linux::symlinkat(src.as_ptr(), self.fd.raw(), dst.as_ptr())
+        })?;
+        Ok(())
+    }
+
+    pub fn read_dir(&self) -> io::Result<ReadDir> {
+        // `getdents64` reads from the file offset of the descriptor, so give
+        // every iterator its own open file description of the directory.
+        let dir = Dir::open_at_c(self.fd.raw(),
+                                 CStr::from_bytes_with_nul(b".\0").unwrap(),
+                                 0,
+                                 self.root.clone())?;
+        Ok(ReadDir::new(dir.fd, dir.root))
+    }
+
+    pub fn root(&self) -> &Path { &self.root }
+
+    pub fn fd(&self) -> &FileDesc { &self.fd }
+
+    pub fn into_fd(self) -> FileDesc { self.fd }
+}
+
+impl FromInner<(c_int, PathBuf)> for Dir {
+    fn from_inner((fd, root): (c_int, PathBuf)) -> Dir {
+        Dir { fd: FileDesc::new(fd), root: root }
+    }
+}
+
+impl fmt::Debug for Dir {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Dir")
+         .field("fd", &self.fd.raw())
+         .field("path", &self.root)
+         .finish()
+    }
+}
+
+pub struct AtomicFile {
+    dir: Dir,
+    path: PathBuf,
+    name: OsString,
+    // Name of the temporary file in `dir`, once it has one
+    temp: Option<OsString>,
+}
+
+impl AtomicFile {
+    /// Creates the file that will replace `p`, in the same directory so it
+    /// can be renamed over `p`. The file is unnamed if the filesystem
+    /// supports `O_TMPFILE`, otherwise it gets a unique hidden name.
+    pub fn new(p: &Path) -> io::Result<(File, AtomicFile)> {
+        let name = match p.file_name() {
+            Some(name) => name.to_owned(),
+            None => return Err(Error::new(ErrorKind::InvalidInput,
+                                          "path doesn't name a file")),
+        };
+        let dir = match p.parent() {
+            Some(parent) if parent != Path::new("") => Dir::open(parent)?,
+            _ => Dir::open(Path::new("."))?,
+        };
+        let mut atomic = AtomicFile {
+            dir: dir,
+            path: p.to_owned(),
+            name: name,
+            temp: None,
+        };
+
+        let dot = cstr(Path::new("."))?;
+        let flags = linux::O_TMPFILE | linux::O_RDWR | linux::O_CLOEXEC;
+        match cvt_r(|| unsafe {
+            linux::openat(atomic.dir.fd.raw(), dot.as_ptr(), flags, 0o666)
+        }) {
+            Ok(fd) => return Ok((File(FileDesc::new(fd as c_int)), atomic)),
+            // Kernels older than 3.11 ignore `__O_TMPFILE` and try to open
+            // the directory for writing, hence `EISDIR`
+            Err(ref e) if e.raw_os_error() == Some(errno::EOPNOTSUPP) ||
+                          e.raw_os_error() == Some(errno::EISDIR) ||
+                          e.raw_os_error() == Some(errno::EINVAL) => {}
+            Err(e) => return Err(e),
+        }
+
+        let mut opts = OpenOptions::new();
+        opts.read(true);
+        opts.write(true);
+        opts.create_new(true);
+        loop {
+            let temp = temp_name(&atomic.name);
+            match atomic.dir.open_at(Path::new(&temp), &opts) {
+                Ok(file) => {
+                    atomic.temp = Some(temp);
+                    return Ok((file, atomic));
+                }
+                Err(ref e) if e.kind() == ErrorKind::AlreadyExists => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Moves `file` into place. The caller is expected to have synced it.
+    ///
+    /// With `replace` unset this fails with `AlreadyExists` instead of
+    /// replacing an existing file.
+    pub fn commit(&mut self, file: &File, replace: bool) -> io::Result<()> {
+        if self.temp.is_none() {
+            self.temp = Some(self.link(file)?);
+        }
+        {
+            let temp = Path::new(self.temp.as_ref().unwrap());
+            let name = Path::new(&self.name);
+            if replace {
+                self.dir.rename_at(temp, &self.dir, name, 0)?;
+            } else {
+                // `linkat` never replaces the target, and unlike
+                // `RENAME_NOREPLACE` doesn't need Linux 3.15
+                self.dir.link_at(temp, &self.dir, name)?;
+                self.dir.unlink_at(temp)?;
+            }
+        }
+        self.temp = None;
+        // Make the new directory entry itself durable
+        cvt_r(|| unsafe { linux::fsync(self.dir.fd.raw()) })?;
+        Ok(())
+    }
+
+    // Gives an `O_TMPFILE` file a unique temporary name. `linkat` can't
+    // replace an existing file, so this doesn't link to the final name.
+    fn link(&self, file: &File) -> io::Result<OsString> {
+        let empty = CString::new("")?;
+        let proc_path = CString::new(format!("/proc/self/fd/{}", file.0.raw()))?;
+        loop {
+            let temp = temp_name(&self.name);
+            let c_temp = cstr(Path::new(&temp))?;
+            let dirfd = self.dir.fd.raw();
+            let mut ret = unsafe {
+                linux::linkat(file.0.raw(),
+                              empty.as_ptr(),
+                              dirfd,
+                              c_temp.as_ptr(),
+                              linux::AT_EMPTY_PATH)
+            };
+            if ret == -errno::ENOENT as isize {
+                // `AT_EMPTY_PATH` needs `CAP_DAC_READ_SEARCH`, following the
+                // magic link in /proc doesn't
+                ret = unsafe {
+                    linux::linkat(linux::AT_FDCWD,
+                                  proc_path.as_ptr(),
+                                  dirfd,
+                                  c_temp.as_ptr(),
+                                  linux::AT_SYMLINK_FOLLOW)
+                };
+            }
+            match cvt(ret) {
+                Ok(_) => return Ok(temp),
+                Err(ref e) if e.kind() == ErrorKind::AlreadyExists => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        if let Some(ref temp) = self.temp {
+            let _ = self.dir.unlink_at(Path::new(temp));
+        }
+    }
+}
+
+impl fmt::Debug for AtomicFile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AtomicFile")
+         .field("path", &self.path)
+         .field("temp", &self.temp)
+         .finish()
+    }
+}
+
+// `.<name>.<unique>.tmp`, unique enough that collisions are rare; callers
+// retry with a new name on `EEXIST` anyway
+fn temp_name(name: &OsStr) -> OsString {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    let nsec = SystemTime::now().as_inner().tv_nsec as usize;
+    let mut temp = OsString::from(".");
+    temp.push(name);
+    temp.push(format!(".{:x}.tmp", nsec ^ count.rotate_left(20)));
+    temp
+}
+
 fn cstr(path: &Path) -> io::Result<CString> {
     Ok(CString::new(path.as_os_str().as_bytes())?)
 }
@@ -474,6 +1052,171 @@ pub fn set_perm(p: &Path, perm: FilePermissions) -> io::Result<()> {
     Ok(())
 }
 
+pub fn set_times(p: &Path, times: FileTimes, follow: bool) -> io::Result<()> {
+    let p = cstr(p)?;
+    let flags = if follow { 0 } else { linux::AT_SYMLINK_NOFOLLOW };
+    cvt(unsafe {
+        linux::utimensat(linux::AT_FDCWD, p.as_ptr(), times.0.as_ptr(), flags)
+    })?;
+    Ok(())
+}
+
+// `None` leaves the respective ID unchanged, which the kernel expects as -1.
+fn id(id: Option<u32>) -> u32 {
+    id.unwrap_or(!0)
+}
+
+pub fn chown(p: &Path, uid: Option<u32>, gid: Option<u32>, follow: bool)
+    -> io::Result<()>
+{
+    let p = cstr(p)?;
+    let flags = if follow { 0 } else { linux::AT_SYMLINK_NOFOLLOW };
+    cvt(unsafe {
+        linux::fchownat(linux::AT_FDCWD, p.as_ptr(), id(uid), id(gid), flags)
+    })?;
+    Ok(())
+}
+
+pub fn memfd_create(name: &OsStr, allow_sealing: bool) -> io::Result<File> {
+    let name = CString::new(name.as_bytes())?;
+    let mut flags = linux::MFD_CLOEXEC;
+    if allow_sealing {
+        flags |= linux::MFD_ALLOW_SEALING;
+    }
+    let fd = cvt(unsafe { linux::memfd_create(name.as_ptr(), flags) })?;
+    Ok(File(FileDesc::new(fd as c_int)))
+}
+
+pub fn fchown(fd: c_int, uid: Option<u32>, gid: Option<u32>)
+    -> io::Result<()>
+{
+    cvt(unsafe { linux::fchown(fd as u32, id(uid), id(gid)) })?;
+    Ok(())
+}
+
+/// The object an extended attribute call operates on.
+pub enum XattrTarget<'a> {
+    /// A path, following a final symlink.
+    Path(&'a Path),
+    /// A path, operating on a final symlink itself.
+    LinkPath(&'a Path),
+    /// An open file descriptor.
+    Fd(c_int),
+}
+
+enum XattrTargetC {
+    Path(CString),
+    LinkPath(CString),
+    Fd(c_int),
+}
+
+impl<'a> XattrTarget<'a> {
+    fn to_c(&self) -> io::Result<XattrTargetC> {
+        Ok(match *self {
+            XattrTarget::Path(p) => XattrTargetC::Path(cstr(p)?),
+            XattrTarget::LinkPath(p) => XattrTargetC::LinkPath(cstr(p)?),
+            XattrTarget::Fd(fd) => XattrTargetC::Fd(fd),
+        })
+    }
+}
+
+// Runs a size-returning xattr call once with an empty buffer to learn the
+// size, then again with a buffer of that size. The value can grow between
+// the two calls, in which case the kernel returns `ERANGE` and we start over.
+// The buffer always has room for a byte, as a size of 0 would make the second
+// call another probe, returning the size rather than filling the buffer.
+fn xattr_buf<F>(f: F) -> io::Result<Vec<u8>>
+    where F: Fn(*mut u8, usize) -> isize
+{
+    loop {
+        let size = cvt(f(ptr::null_mut(), 0))?;
+        let mut buf = Vec::with_capacity(cmp::max(size, 1));
+        match cvt(f(buf.as_mut_ptr(), buf.capacity())) {
+            Ok(len) if len <= buf.capacity() => {
+                unsafe { buf.set_len(len); }
+                return Ok(buf);
+            }
+            Ok(_) => {}
+            Err(ref e) if e.raw_os_error() == Some(errno::ERANGE) => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+pub fn getxattr(target: &XattrTarget, name: &OsStr)
+    -> io::Result<Option<Vec<u8>>>
+{
+    let target = target.to_c()?;
+    let name = CString::new(name.as_bytes())?;
+    let name = name.as_ptr();
+    let value = xattr_buf(|buf, size| unsafe {
+        let buf = buf as *mut _;
+        match target {
+            XattrTargetC::Path(ref p) =>
+                linux::getxattr(p.as_ptr(), name, buf, size),
+            XattrTargetC::LinkPath(ref p) =>
+                linux::lgetxattr(p.as_ptr(), name, buf, size),
+            XattrTargetC::Fd(fd) => linux::fgetxattr(fd, name, buf, size),
+        }
+    });
+    match value {
+        Ok(value) => Ok(Some(value)),
+        Err(ref e) if e.raw_os_error() == Some(errno::ENODATA) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn setxattr(target: &XattrTarget, name: &OsStr, value: &[u8], flags: c_int)
+    -> io::Result<()>
+{
+    let target = target.to_c()?;
+    let name = CString::new(name.as_bytes())?;
+    let name = name.as_ptr();
+    let buf = value.as_ptr() as *const _;
+    let size = value.len();
+    cvt(unsafe {
+        match target {
+            XattrTargetC::Path(ref p) =>
+                linux::setxattr(p.as_ptr(), name, buf, size, flags),
+            XattrTargetC::LinkPath(ref p) =>
+                linux::lsetxattr(p.as_ptr(), name, buf, size, flags),
+            XattrTargetC::Fd(fd) =>
+                linux::fsetxattr(fd, name, buf, size, flags),
+        }
+    })?;
+    Ok(())
+}
+
+pub fn removexattr(target: &XattrTarget, name: &OsStr) -> io::Result<()> {
+    let target = target.to_c()?;
+    let name = CString::new(name.as_bytes())?;
+    let name = name.as_ptr();
+    cvt(unsafe {
+        match target {
+            XattrTargetC::Path(ref p) => linux::removexattr(p.as_ptr(), name),
+            XattrTargetC::LinkPath(ref p) =>
+                linux::lremovexattr(p.as_ptr(), name),
+            XattrTargetC::Fd(fd) => linux::fremovexattr(fd, name),
+        }
+    })?;
+    Ok(())
+}
+
+/// Returns the names of the extended attributes of `target` as a list of
+/// NUL terminated strings.
+pub fn listxattr(target: &XattrTarget) -> io::Result<Vec<u8>> {
+    let target = target.to_c()?;
+    xattr_buf(|buf, size| unsafe {
+        let buf = buf as *mut _;
+        match target {
+            XattrTargetC::Path(ref p) => linux::listxattr(p.as_ptr(), buf, size),
+            XattrTargetC::LinkPath(ref p) =>
+                linux::llistxattr(p.as_ptr(), buf, size),
+            XattrTargetC::Fd(fd) => linux::flistxattr(fd, buf, size),
+        }
+    })
+}
+
 pub fn rmdir(p: &Path) -> io::Result<()> {
     let p = cstr(p)?;
     cvt(unsafe { linux::rmdir(p.as_ptr()) })?;
@@ -485,20 +1228,33 @@ pub fn remove_dir_all(path: &Path) -> io::Result<()> {
     if filetype.is_symlink() {
         unlink(path)
     } else {
-        remove_dir_all_recursive(path)
+        // If `path` was replaced by a symlink after the `lstat` above,
+        // `O_NOFOLLOW` makes this fail instead of descending into whatever
+        // the symlink points at.
+        remove_dir_all_recursive(&Dir::open_nofollow(path)?)?;
+        rmdir(path)
     }
 }
 
-fn remove_dir_all_recursive(path: &Path) -> io::Result<()> {
-    for child in readdir(path)? {
+// Everything below `dir` is addressed relative to an open directory file
+// descriptor, so renaming or swapping an already opened directory with a
+// symlink can't redirect the removal to somewhere else.
+fn remove_dir_all_recursive(dir: &Dir) -> io::Result<()> {
+    for child in dir.read_dir()? {
         let child = child?;
-        if child.file_type()?.is_dir() {
-            remove_dir_all_recursive(&child.path())?;
+        let name = Path::new(&child.name);
+        let is_dir = match child.entry.d_type {
+            linux::DT_UNKNOWN => dir.lstat_at(name)?.file_type().is_dir(),
+            d_type => d_type == linux::DT_DIR,
+        };
+        if is_dir {
+            remove_dir_all_recursive(&dir.open_dir_at(name)?)?;
+            dir.rmdir_at(name)?;
         } else {
-            unlink(&child.path())?;
+            dir.unlink_at(name)?;
         }
     }
-    rmdir(path)
+    Ok(())
 }
 
 pub fn readlink(p: &Path) -> io::Result<PathBuf> {
@@ -569,7 +1325,13 @@ pub fn canonicalize(p: &Path) -> io::Result<PathBuf> {
     let fd_stat = file.file_attr()?;
     let fd = file.into_fd();
 
-    let canonical = readlink(Path::new(&format!("/proc/self/fd/{}", fd.raw())))?;
+    let proc_path = format!("/proc/self/fd/{}", fd.raw());
+    let canonical = match readlink(Path::new(&proc_path)) {
+        Ok(canonical) => canonical,
+        // `/proc` isn't mounted, as is common in chroots and initramfs
+        Err(ref e) if e.raw_os_error() == Some(errno::ENOENT) => resolve(p)?,
+        Err(e) => return Err(e),
+    };
     let canonical_stat = stat(&canonical)?;
 
     if fd_stat.stat.st_dev != canonical_stat.stat.st_dev ||
@@ -581,6 +1343,60 @@ pub fn canonicalize(p: &Path) -> io::Result<PathBuf> {
     Ok(canonical)
 }
 
+// Resolves `p` to an absolute path without `.`, `..` or symlinks the slow
+// way: one component at a time, reading every symlink on the way and
+// splicing its target into the rest of the path.
+fn resolve(p: &Path) -> io::Result<PathBuf> {
+    // Same limit as the kernel's path walk
+    const MAX_LINKS: usize = 40;
+
+    let mut resolved = if p.is_absolute() {
+        PathBuf::from("/")
+    } else {
+        os::getcwd()?
+    };
+    // Components still to resolve, next one last
+    let mut pending = vec![];
+    push_components(&mut pending, p);
+    let mut links = 0;
+    while let Some(component) = pending.pop() {
+        if &*component == ".." {
+            resolved.pop();
+            continue;
+        }
+        resolved.push(&component);
+        let file_type = lstat(&resolved)?.file_type();
+        if file_type.is_symlink() {
+            links += 1;
+            if links > MAX_LINKS {
+                return Err(io::Error::from_raw_os_error(errno::ELOOP));
+            }
+            let target = readlink(&resolved)?;
+            if target.is_absolute() {
+                resolved = PathBuf::from("/");
+            } else {
+                resolved.pop();
+            }
+            push_components(&mut pending, &target);
+        } else if !pending.is_empty() && !file_type.is_dir() {
+            return Err(io::Error::from_raw_os_error(errno::ENOTDIR));
+        }
+    }
+    Ok(resolved)
+}
+
+// Pushes the `..` and normal components of `p` in reverse order, so that
+// popping yields them in order.
+fn push_components(pending: &mut Vec<OsString>, p: &Path) {
+    for component in p.components().rev() {
+        match component {
+            Component::ParentDir => pending.push(OsString::from("..")),
+            Component::Normal(name) => pending.push(name.to_owned()),
+            Component::CurDir | Component::RootDir | Component::Prefix(_) => {}
+        }
+    }
+}
+
 pub fn copy(from: &Path, to: &Path) -> io::Result<u64> {
     use fs::{File, set_permissions};
     if !from.is_file() {
@@ -590,9 +1406,134 @@ pub fn copy(from: &Path, to: &Path) -> io::Result<u64> {
 
     let mut reader = File::open(from)?;
     let mut writer = File::create(to)?;
-    let perm = reader.metadata()?.permissions();
-
-    let ret = io::copy(&mut reader, &mut writer)?;
+    let metadata = reader.metadata()?;
+    let perm = metadata.permissions();
+    let len = metadata.len();
+
+    let ret = if len == 0 {
+        // Files in procfs and sysfs report a size of zero but aren't empty;
+        // only plain reads are guaranteed to work on them.
+        io::copy(&mut reader, &mut writer)?
+    } else if reflink(&reader, &writer) {
+        len
+    } else if (metadata.as_inner().stat.st_blocks as u64) * 512 < len {
+        copy_sparse(&reader, &writer, len)?
+    } else {
+        // The file may have grown since `metadata`, so read on to the end.
+        copy_range(&reader, &writer, len)? + io::copy(&mut reader, &mut writer)?
+    };
     set_permissions(to, perm)?;
     Ok(ret)
 }
+
+// Makes `writer` share the extents of `reader` (a reflink), which only works
+// when both are on the same copy-on-write filesystem. Nothing has been
+// written to `writer` if this fails.
+fn reflink(reader: &fs::File, writer: &fs::File) -> bool {
+    let (r, w) = (reader.as_inner().0.raw(), writer.as_inner().0.raw());
+    unsafe { linux::ioctl(w, linux::FICLONE, r as c_ulong) == 0 }
+}
+
+// Copies only the data regions of `reader`, seeking over its holes, so that
+// `writer` ends up with holes in the same places instead of blocks of zeros.
+fn copy_sparse(reader: &fs::File, writer: &fs::File, len: u64)
+    -> io::Result<u64>
+{
+    let (r, w) = (reader.as_inner(), writer.as_inner());
+    let mut pos = 0;
+    loop {
+        let start = match r.seek_data(pos) {
+            Ok(Some(start)) => start,
+            Ok(None) => break,
+            // Kernels older than 3.1 don't know about `SEEK_DATA`.
+            Err(ref e) if pos == 0 && e.raw_os_error() == Some(errno::EINVAL) => {
+                return copy_range(reader, writer, len);
+            }
+            Err(e) => return Err(e),
+        };
+        let end = r.seek_hole(start)?;
+        r.seek(SeekFrom::Start(start))?;
+        w.seek(SeekFrom::Start(start))?;
+        copy_range(reader, writer, end - start)?;
+        pos = end;
+    }
+    // Recreates the hole at the end of the file, if any.
+    w.truncate(len)?;
+    Ok(len)
+}
+
+// Copies up to `len` bytes from the current offset of `reader` to the current
+// offset of `writer`, preferably inside of the kernel: with `copy_file_range`
+// first and `sendfile` second. Both advance the file offsets, so whenever one
+// of them fails because it's not supported by the kernel or by the
+// filesystems involved, or copies nothing although `len` isn't reached yet,
+// the next one picks up where it stopped. Whatever is left after that goes
+// through a userspace buffer.
+fn copy_range(reader: &fs::File, writer: &fs::File, len: u64)
+    -> io::Result<u64>
+{
+    // `copy_file_range` appeared in Linux 4.5; remember once it's missing so
+    // we don't keep on issuing a system call that's doomed to fail.
+    static HAS_COPY_FILE_RANGE: AtomicBool = AtomicBool::new(true);
+    // Neither call copies more than this in one go, see `MAX_RW_COUNT`.
+    const CHUNK: u64 = 0x7fff_f000;
+
+    let (r, w) = (reader.as_inner().0.raw(), writer.as_inner().0.raw());
+    let fallback = |e: &io::Error| match e.raw_os_error() {
+        Some(errno::ENOSYS) |
+        Some(errno::EXDEV) |
+        Some(errno::EINVAL) |
+        Some(errno::EOPNOTSUPP) => true,
+        _ => false,
+    };
+
+    let mut written = 0;
+    if HAS_COPY_FILE_RANGE.load(Ordering::Relaxed) {
+        while written < len {
+            let chunk = cmp::min(len - written, CHUNK) as usize;
+            match cvt_r(|| unsafe {
+                linux::copy_file_range(r, ptr::null_mut(),
+                                       w, ptr::null_mut(),
+                                       chunk, 0)
+            }) {
+                // Linux 5.3 to 5.18 copy nothing from procfs and sysfs,
+                // whatever their size claims.
+                Ok(0) => break,
+                Ok(n) => written += n as u64,
+                Err(ref e) if fallback(e) => {
+                    if e.raw_os_error() == Some(errno::ENOSYS) {
+                        HAS_COPY_FILE_RANGE.store(false, Ordering::Relaxed);
+                    }
+                    break;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    while written < len {
+        let chunk = cmp::min(len - written, CHUNK) as usize;
+        match cvt_r(|| unsafe {
+            linux::sendfile64(w, r, ptr::null_mut(), chunk)
+        }) {
+            Ok(0) => break,
+            Ok(n) => written += n as u64,
+            Err(ref e) if fallback(e) => break,
+            Err(e) => return Err(e),
+        }
+    }
+
+    let mut buf = [0; DEFAULT_BUF_SIZE];
+    while written < len {
+        let chunk = cmp::min(len - written, buf.len() as u64) as usize;
+        let n = match (&*reader).read(&mut buf[..chunk]) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        (&*writer).write_all(&buf[..n])?;
+        written += n as u64;
+    }
+    Ok(written)
+}
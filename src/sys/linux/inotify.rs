@@ -0,0 +1,49 @@
+use ctypes::c_int;
+use ffi::CString;
+use io;
+use linux;
+use path::Path;
+use sys::ext::ffi::OsStrExt;
+use sys::fd::FileDesc;
+use sys::FromInner;
+use super::cvt;
+
+pub struct Inotify(FileDesc);
+
+impl Inotify {
+    pub fn new() -> io::Result<Inotify> {
+        let fd = cvt(unsafe { linux::inotify_init1(linux::IN_CLOEXEC) })?;
+        Ok(Inotify(FileDesc::new(fd as c_int)))
+    }
+
+    pub fn add_watch(&self, p: &Path, mask: u32) -> io::Result<c_int> {
+        let p = CString::new(p.as_os_str().as_bytes())?;
+        let wd = cvt(unsafe {
+            linux::inotify_add_watch(self.0.raw(), p.as_ptr(), mask)
+        })?;
+        Ok(wd as c_int)
+    }
+
+    pub fn rm_watch(&self, wd: c_int) -> io::Result<()> {
+        cvt(unsafe { linux::inotify_rm_watch(self.0.raw(), wd) })?;
+        Ok(())
+    }
+
+    pub fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.0.set_nonblocking(nonblocking)
+    }
+
+    pub fn fd(&self) -> &FileDesc { &self.0 }
+
+    pub fn into_fd(self) -> FileDesc { self.0 }
+}
+
+impl FromInner<c_int> for Inotify {
+    fn from_inner(fd: c_int) -> Inotify {
+        Inotify(FileDesc::new(fd))
+    }
+}
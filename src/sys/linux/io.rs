@@ -1,7 +1,15 @@
+use cmp;
+use ctypes::{c_int, c_long, c_uint, c_ulong};
 use io::ErrorKind;
+use io::IoSlice;
 use io::Read;
 use io;
+use linux::{self, loff_t, pollfd, stat64, time_t, timespec};
+use mem;
+use ptr;
 use slice::from_raw_parts_mut;
+use sys::{cvt, errno};
+use time::{Duration, Instant};
 
 pub const DEFAULT_BUF_SIZE: usize = 8 * 1024;
 
@@ -42,3 +50,100 @@ pub unsafe fn read_to_end_uninitialized(r: &mut Read, buf: &mut Vec<u8>) -> io::
         }
     }
 }
+
+pub fn pipe() -> io::Result<(c_int, c_int)> {
+    let mut fds = [0; 2];
+    cvt(unsafe { linux::pipe2(fds.as_mut_ptr(), linux::O_CLOEXEC) })?;
+    Ok((fds[0], fds[1]))
+}
+
+pub fn splice(fd_in: c_int,
+              off_in: Option<&mut u64>,
+              fd_out: c_int,
+              off_out: Option<&mut u64>,
+              len: usize,
+              flags: c_uint)
+              -> io::Result<usize> {
+    let off_in = off_in.map_or(ptr::null_mut(),
+                               |o| o as *mut u64 as *mut loff_t);
+    let off_out = off_out.map_or(ptr::null_mut(),
+                                 |o| o as *mut u64 as *mut loff_t);
+    cvt(unsafe { linux::splice(fd_in, off_in, fd_out, off_out, len, flags) })
+}
+
+pub fn tee(fd_in: c_int, fd_out: c_int, len: usize, flags: c_uint)
+           -> io::Result<usize> {
+    cvt(unsafe { linux::tee(fd_in, fd_out, len, flags) })
+}
+
+pub fn vmsplice(fd: c_int, bufs: &[IoSlice], flags: c_uint)
+                -> io::Result<usize> {
+    let nr_segs = cmp::min(bufs.len(), linux::UIO_MAXIOV) as c_ulong;
+    let iov = bufs.as_ptr() as *const linux::iovec;
+    cvt(unsafe { linux::vmsplice(fd, iov, nr_segs, flags) })
+}
+
+fn is_pipe(fd: c_int) -> bool {
+    let mut stat: stat64 = unsafe { mem::zeroed() };
+    if unsafe { linux::fstat64(fd, &mut stat) } < 0 {
+        return false;
+    }
+    stat.st_mode as c_uint & linux::S_IFMT == linux::S_IFIFO
+}
+
+// Moves everything from `fd_in` to `fd_out` inside the kernel, as
+// `io::copy` would through a buffer.
+//
+// `splice` needs a pipe on at least one side and refuses some other
+// combinations with `EINVAL`, e.g. an output file opened with `O_APPEND`.
+// `None` is returned in those cases, before anything was transferred, so the
+// caller can fall back to copying through userspace.
+pub fn splice_copy(fd_in: c_int, fd_out: c_int) -> Option<io::Result<u64>> {
+    // Asking for more than the pipe holds is fine, `splice` returns early
+    const CHUNK: usize = 0x4000_0000;
+
+    if !is_pipe(fd_in) && !is_pipe(fd_out) {
+        return None;
+    }
+    let mut written = 0;
+    loop {
+        match splice(fd_in, None, fd_out, None, CHUNK, linux::SPLICE_F_MOVE) {
+            Ok(0) => return Some(Ok(written)),
+            Ok(n) => written += n as u64,
+            Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(ref e) if written == 0 &&
+                          (e.raw_os_error() == Some(errno::EINVAL) ||
+                           e.raw_os_error() == Some(errno::ENOSYS)) => {
+                return None;
+            }
+            Err(e) => return Some(Err(e)),
+        }
+    }
+}
+
+// Waits with `ppoll`, as some architectures have no `poll`. When a signal
+// interrupts the wait it's restarted with whatever is left of `timeout`.
+pub fn poll(fds: &mut [pollfd], timeout: Option<Duration>)
+            -> io::Result<usize> {
+    let start = Instant::now();
+    loop {
+        let mut ts = timespec { tv_sec: 0, tv_nsec: 0 };
+        let tsp = match timeout {
+            Some(timeout) => {
+                let left = timeout.checked_sub(start.elapsed())
+                    .unwrap_or(Duration::new(0, 0));
+                let secs = cmp::min(left.as_secs(), time_t::max_value() as u64);
+                ts.tv_sec = secs as time_t;
+                ts.tv_nsec = left.subsec_nanos() as c_long;
+                &mut ts as *mut timespec
+            }
+            None => ptr::null_mut(),
+        };
+        match cvt(unsafe {
+            linux::ppoll(fds.as_mut_ptr(), fds.len() as c_uint, tsp)
+        }) {
+            Err(ref e) if e.kind() == ErrorKind::Interrupted => {}
+            other => return other,
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use cmp;
+use ctypes::c_int;
+use io::{self, Error};
+use linux::{self, io_uring_cqe, io_uring_params, io_uring_sqe};
+use mem;
+use ptr;
+use sync::atomic::{fence, Ordering};
+use sys::errno;
+use sys::fd::FileDesc;
+use sys::mmap::Mmap;
+use super::cvt;
+
+// The rings are shared with the kernel, which updates the SQ head and the
+// CQ tail concurrently. Only the side that owns an index writes it, so
+// plain volatile accesses ordered by fences are enough, like liburing's
+// `io_uring_smp_load_acquire` and `io_uring_smp_store_release`.
+unsafe fn load_acquire(p: *const u32) -> u32 {
+    let v = ptr::read_volatile(p);
+    fence(Ordering::Acquire);
+    v
+}
+
+unsafe fn store_release(p: *mut u32, v: u32) {
+    fence(Ordering::Release);
+    ptr::write_volatile(p, v);
+}
+
+pub struct IoUring {
+    fd: FileDesc,
+    // Kept alive for the pointers below
+    _sq_ring: Mmap,
+    _cq_ring: Option<Mmap>,
+    _sqes: Mmap,
+    sq_head: *const u32,
+    sq_tail: *mut u32,
+    sq_mask: u32,
+    sq_entries: u32,
+    sq_array: *mut u32,
+    sqes: *mut io_uring_sqe,
+    cq_head: *mut u32,
+    cq_tail: *const u32,
+    cq_mask: u32,
+    cq_entries: u32,
+    cqes: *const io_uring_cqe,
+    // Our copy of the SQ tail, only published to the kernel in `push`
+    tail: u32,
+}
+
+unsafe impl Send for IoUring {}
+
+impl IoUring {
+    pub fn new(entries: u32) -> io::Result<IoUring> {
+        let mut p: io_uring_params = unsafe { mem::zeroed() };
+        let fd = cvt(unsafe { linux::io_uring_setup(entries, &mut p) })?;
+        let fd = FileDesc::new(fd as c_int);
+
+        // The read, write, openat, statx and close opcodes came with Linux
+        // 5.6, as did this feature bit. Earlier versions would fail each
+        // operation with EINVAL, so report them like kernels without
+        // io_uring at all.
+        if p.features & linux::IORING_FEAT_RW_CUR_POS == 0 {
+            return Err(Error::from_raw_os_error(errno::ENOSYS));
+        }
+
+        let sq_len = p.sq_off.array as usize +
+                     p.sq_entries as usize * mem::size_of::<u32>();
+        let cq_len = p.cq_off.cqes as usize +
+                     p.cq_entries as usize * mem::size_of::<io_uring_cqe>();
+        let sqes_len = p.sq_entries as usize * mem::size_of::<io_uring_sqe>();
+
+        // Since Linux 5.4 both rings live in a single mapping
+        let single = p.features & linux::IORING_FEAT_SINGLE_MMAP != 0;
+        let sq_len = if single { cmp::max(sq_len, cq_len) } else { sq_len };
+        let sq_ring = Mmap::kernel(&fd, linux::IORING_OFF_SQ_RING, sq_len)?;
+        let cq_ring = if single {
+            None
+        } else {
+            Some(Mmap::kernel(&fd, linux::IORING_OFF_CQ_RING, cq_len)?)
+        };
+        let sqes = Mmap::kernel(&fd, linux::IORING_OFF_SQES, sqes_len)?;
+
+        let sq = sq_ring.ptr();
+        let cq = cq_ring.as_ref().map_or(sq, |m| m.ptr());
+        unsafe {
+            let sq_tail = sq.offset(p.sq_off.tail as isize) as *mut u32;
+            Ok(IoUring {
+                sq_head: sq.offset(p.sq_off.head as isize) as *const u32,
+                sq_tail: sq_tail,
+                sq_mask: *(sq.offset(p.sq_off.ring_mask as isize)
+                           as *const u32),
+                sq_entries: p.sq_entries,
+                sq_array: sq.offset(p.sq_off.array as isize) as *mut u32,
+                sqes: sqes.ptr() as *mut io_uring_sqe,
+                cq_head: cq.offset(p.cq_off.head as isize) as *mut u32,
+                cq_tail: cq.offset(p.cq_off.tail as isize) as *const u32,
+                cq_mask: *(cq.offset(p.cq_off.ring_mask as isize)
+                           as *const u32),
+                cq_entries: p.cq_entries,
+                cqes: cq.offset(p.cq_off.cqes as isize)
+                        as *const io_uring_cqe,
+                tail: *sq_tail,
+                fd: fd,
+                _sq_ring: sq_ring,
+                _cq_ring: cq_ring,
+                _sqes: sqes,
+            })
+        }
+    }
+
+    pub fn sq_entries(&self) -> u32 { self.sq_entries }
+
+    pub fn cq_entries(&self) -> u32 { self.cq_entries }
+
+    // Entries pushed but not yet consumed by the kernel
+    pub fn queued(&self) -> u32 {
+        self.tail.wrapping_sub(unsafe { load_acquire(self.sq_head) })
+    }
+
+    // Completions not yet taken off the CQ
+    pub fn ready(&self) -> u32 {
+        unsafe {
+            let head = ptr::read_volatile(self.cq_head);
+            load_acquire(self.cq_tail).wrapping_sub(head)
+        }
+    }
+
+    /// Queues `sqe`, returning `false` if the SQ is full. Nothing is passed
+    /// to the kernel until `enter`.
+    ///
+    /// Unsafe because the kernel reads and writes the memory `sqe` points
+    /// to until its completion has been reaped.
+    pub unsafe fn push(&mut self, sqe: &io_uring_sqe) -> bool {
+        if self.queued() == self.sq_entries {
+            return false;
+        }
+        let index = self.tail & self.sq_mask;
+        ptr::write(self.sqes.offset(index as isize), *sqe);
+        ptr::write_volatile(self.sq_array.offset(index as isize), index);
+        self.tail = self.tail.wrapping_add(1);
+        store_release(self.sq_tail, self.tail);
+        true
+    }
+
+    /// Takes back the entry pushed last, returning `false` if the kernel
+    /// already consumed it. The kernel consumes entries in order, so the
+    /// last one is still there as long as any are.
+    pub fn retract(&mut self) -> bool {
+        if self.queued() == 0 {
+            return false;
+        }
+        self.tail = self.tail.wrapping_sub(1);
+        unsafe { store_release(self.sq_tail, self.tail) };
+        true
+    }
+
+    /// Submits the queued entries and waits until at least `min_complete`
+    /// completions are available. Returns the number of entries submitted.
+    ///
+    /// A signal ends the wait early without an error once anything was
+    /// submitted, so callers needing the completions check `ready`.
+    pub fn enter(&mut self, min_complete: u32) -> io::Result<usize> {
+        let flags = if min_complete > 0 {
+            linux::IORING_ENTER_GETEVENTS
+        } else {
+            0
+        };
+        loop {
+            // Once anything was submitted the kernel returns how many
+            // instead of EINTR, so retrying can't submit twice
+            let ret = unsafe {
+                linux::io_uring_enter(self.fd.raw(),
+                                      self.queued(),
+                                      min_complete,
+                                      flags)
+            };
+            match cvt(ret) {
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                other => return other,
+            }
+        }
+    }
+
+    /// Takes the next completion off the CQ, if there is one.
+    pub fn pop(&mut self) -> Option<io_uring_cqe> {
+        unsafe {
+            let head = ptr::read_volatile(self.cq_head);
+            if head == load_acquire(self.cq_tail) {
+                return None;
+            }
+            let index = head & self.cq_mask;
+            let cqe = ptr::read(self.cqes.offset(index as isize));
+            store_release(self.cq_head, head.wrapping_add(1));
+            Some(cqe)
+        }
+    }
+
+    pub fn fd(&self) -> &FileDesc { &self.fd }
+}
@@ -0,0 +1,163 @@
+use ctypes::c_int;
+use io::{self, Error, ErrorKind};
+use linux;
+use sys::fd::FileDesc;
+use sys::fs::File;
+use sys::os;
+use super::cvt;
+
+pub struct Mmap {
+    ptr: *mut u8,
+    len: usize,
+    // Distance between `ptr` and the page boundary the map starts at
+    align: usize,
+    // The file backing the map and the offset of `ptr` in it
+    file: Option<(File, u64)>,
+}
+
+// `mmap` returns an address, which may look negative on 32-bit systems, so
+// only the top 4095 values are errors
+fn cvt_addr(ret: isize) -> io::Result<usize> {
+    if ret < 0 && ret >= -4095 {
+        Err(Error::from_raw_os_error(-ret as i32))
+    } else {
+        Ok(ret as usize)
+    }
+}
+
+impl Mmap {
+    pub fn file(file: &File, offset: u64, len: usize, writable: bool)
+        -> io::Result<Mmap>
+    {
+        let size = file.file_attr()?.size();
+        let end = offset.checked_add(len as u64);
+        if end.map_or(true, |end| end > size) {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                  "memory map extends past the end of the file"));
+        }
+        let prot = if writable {
+            linux::PROT_READ | linux::PROT_WRITE
+        } else {
+            linux::PROT_READ
+        };
+        Mmap::new(len, prot, linux::MAP_SHARED, Some((file.duplicate()?, offset)))
+    }
+
+    pub fn anon(len: usize) -> io::Result<Mmap> {
+        Mmap::new(len,
+                  linux::PROT_READ | linux::PROT_WRITE,
+                  linux::MAP_PRIVATE | linux::MAP_ANONYMOUS,
+                  None)
+    }
+
+    /// Maps a region of a kernel object that isn't a regular file, like the
+    /// rings of an io_uring instance. `offset` selects the region and `fd`
+    /// must outlive the map.
+    pub fn kernel(fd: &FileDesc, offset: u64, len: usize) -> io::Result<Mmap> {
+        Mmap::map(len,
+                  linux::PROT_READ | linux::PROT_WRITE,
+                  linux::MAP_SHARED,
+                  fd.raw(),
+                  offset,
+                  None)
+    }
+
+    fn new(len: usize, prot: i32, flags: i32, file: Option<(File, u64)>)
+        -> io::Result<Mmap>
+    {
+        let (fd, offset) = match file {
+            Some((ref file, offset)) => (file.fd().raw(), offset),
+            None => (-1, 0),
+        };
+        Mmap::map(len, prot, flags, fd, offset, file)
+    }
+
+    fn map(len: usize,
+           prot: i32,
+           flags: i32,
+           fd: c_int,
+           offset: u64,
+           file: Option<(File, u64)>)
+           -> io::Result<Mmap> {
+        if len == 0 {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                  "memory map must have a non-zero length"));
+        }
+        // The offset given to `mmap` has to be page aligned, so map from the
+        // start of the page and hide the bytes before `offset`
+        let align = (offset % os::page_size() as u64) as usize;
+        let addr = cvt_addr(unsafe {
+            linux::mmap(0,
+                        len + align,
+                        prot,
+                        flags,
+                        fd,
+                        (offset - align as u64) as linux::loff_t)
+        })?;
+        Ok(Mmap {
+            ptr: (addr + align) as *mut u8,
+            len: len,
+            align: align,
+            file: file,
+        })
+    }
+
+    pub fn ptr(&self) -> *mut u8 { self.ptr }
+
+    pub fn len(&self) -> usize { self.len }
+
+    fn base(&self) -> usize { self.ptr as usize - self.align }
+
+    pub fn flush(&self, offset: usize, len: usize, sync: bool)
+        -> io::Result<()>
+    {
+        if offset.checked_add(len).map_or(true, |end| end > self.len) {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                                  "range extends past the end of the memory map"));
+        }
+        // `msync` also wants a page aligned start
+        let start = self.ptr as usize + offset;
+        let align = start % os::page_size();
+        let flags = if sync { linux::MS_SYNC } else { linux::MS_ASYNC };
+        cvt(unsafe { linux::msync(start - align, len + align, flags) })?;
+        Ok(())
+    }
+
+    pub fn advise(&self, advice: i32) -> io::Result<()> {
+        cvt(unsafe {
+            linux::madvise(self.base(), self.len + self.align, advice)
+        })?;
+        Ok(())
+    }
+
+    pub fn lock(&self) -> io::Result<()> {
+        cvt(unsafe { linux::mlock(self.base(), self.len + self.align) })?;
+        Ok(())
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        cvt(unsafe { linux::munlock(self.base(), self.len + self.align) })?;
+        Ok(())
+    }
+
+    /// Checks that the whole map is still backed by the file, i.e. that the
+    /// file hasn't been truncated since it was mapped. Touching a page past
+    /// the end of the file raises `SIGBUS`.
+    pub fn check(&self) -> io::Result<()> {
+        if let Some((ref file, offset)) = self.file {
+            if file.file_attr()?.size() < offset + self.len as u64 {
+                return Err(Error::new(ErrorKind::UnexpectedEof,
+                                      "file was truncated below the end of \
+                                       the memory map"));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        // Nothing sensible can be done about errors here
+        let _ = unsafe { linux::munmap(self.base(), self.len + self.align) };
+    }
+}
@@ -1,16 +1,28 @@
+pub mod epoll;
+pub mod eventfd;
 pub mod ext;
 pub mod fd;
 pub mod fs;
+pub mod inotify;
 pub mod io;
+pub mod io_uring;
 pub mod memchr;
+pub mod mmap;
+pub mod ns;
 // Rust 1.14.0
 pub mod os_str;
 // Rust 1.14.0
 pub mod path;
 pub mod pipe;
+pub mod prctl;
 pub mod process;
+pub mod pty;
+pub mod resource;
+pub mod seccomp;
 pub mod os;
+pub mod termios;
 pub mod time;
+pub mod timerfd;
 
 pub use os::linux as platform;
 
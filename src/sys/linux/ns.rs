@@ -0,0 +1,70 @@
+use ctypes::{c_char, c_int, c_ulong};
+use ffi::CStr;
+use io::{self, Write};
+use linux;
+use ptr;
+use sys::fd::FileDesc;
+use super::{cvt, cvt_r};
+
+pub fn unshare(flags: c_int) -> io::Result<()> {
+    // The flags are an `unsigned long`, without sign extension
+    cvt(unsafe { linux::unshare(flags as u32 as c_ulong) }).map(|_| ())
+}
+
+pub fn setns(fd: c_int, nstype: c_int) -> io::Result<()> {
+    cvt(unsafe { linux::setns(fd, nstype) }).map(|_| ())
+}
+
+fn as_ptr(s: Option<&CStr>) -> *const c_char {
+    s.map_or(ptr::null(), |s| s.as_ptr())
+}
+
+pub fn mount(source: &CStr,
+             target: &CStr,
+             fstype: Option<&CStr>,
+             flags: c_ulong,
+             data: Option<&CStr>)
+             -> io::Result<()> {
+    cvt(unsafe {
+        linux::mount(source.as_ptr(),
+                     target.as_ptr(),
+                     as_ptr(fstype),
+                     flags,
+                     as_ptr(data))
+    }).map(|_| ())
+}
+
+pub fn umount2(target: &CStr, flags: c_int) -> io::Result<()> {
+    cvt(unsafe { linux::umount2(target.as_ptr(), flags) }).map(|_| ())
+}
+
+pub fn pivot_root(new_root: &CStr, put_old: &CStr) -> io::Result<()> {
+    cvt(unsafe {
+        linux::pivot_root(new_root.as_ptr(), put_old.as_ptr())
+    }).map(|_| ())
+}
+
+pub fn chroot(path: &CStr) -> io::Result<()> {
+    cvt(unsafe { linux::chroot(path.as_ptr()) }).map(|_| ())
+}
+
+// Writes `data` to `path` in a single `write`, as the files under
+// /proc/<pid>/ that set up user namespaces require
+pub fn write_proc(path: &CStr, data: &[u8]) -> io::Result<()> {
+    const FLAGS: c_int = linux::O_WRONLY | linux::O_CLOEXEC;
+    let fd = cvt_r(|| unsafe { linux::open(path.as_ptr(), FLAGS, 0) })?;
+    let fd = FileDesc::new(fd as c_int);
+    fd.write(data).map(|_| ())
+}
+
+// A line of `inside outside count` per range, to be written at once
+pub fn id_map<I>(ranges: I) -> Vec<u8>
+    where I: Iterator<Item = (u32, u32, u32)>
+{
+    let mut buf = Vec::new();
+    for (inside, outside, count) in ranges {
+        // Writing to a `Vec` can't fail
+        let _ = writeln!(buf, "{} {} {}", inside, outside, count);
+    }
+    buf
+}
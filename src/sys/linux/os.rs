@@ -1,4 +1,23 @@
+use ffi::OsString;
+use io;
 use linux;
+use path::PathBuf;
+use sys::errno;
+use sys::ext::ffi::OsStringExt;
+use sync::atomic::{AtomicUsize, Ordering};
+
+static PAGE_SIZE: AtomicUsize = AtomicUsize::new(0);
+
+/// Picks what we need from the auxiliary vector the kernel passed to the
+/// program. Called once by `rt::start` before `main` runs.
+pub unsafe fn init(mut auxv: *const usize) {
+    while *auxv != linux::AT_NULL {
+        if *auxv == linux::AT_PAGESZ {
+            PAGE_SIZE.store(*auxv.offset(1), Ordering::Relaxed);
+        }
+        auxv = auxv.offset(2);
+    }
+}
 
 pub fn errno() -> i32 {
     panic!("no C-compatible errno variable");
@@ -10,6 +29,31 @@ pub fn error_string(errno: i32) -> String {
     })
 }
 
+pub fn getcwd() -> io::Result<PathBuf> {
+    let mut buf = Vec::with_capacity(512);
+    loop {
+        match super::cvt(unsafe {
+            linux::getcwd(buf.as_mut_ptr() as *mut _, buf.capacity())
+        }) {
+            Ok(len) => {
+                // `len` counts the terminating NUL
+                unsafe { buf.set_len(len - 1); }
+                buf.shrink_to_fit();
+                return Ok(PathBuf::from(OsString::from_vec(buf)));
+            }
+            Err(ref e) if e.raw_os_error() == Some(errno::ERANGE) => {
+                let cap = buf.capacity();
+                buf.reserve(cap);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
 pub fn exit(code: i32) -> ! {
     unsafe { linux::exit_group(code) }
 }
+
+pub fn page_size() -> usize {
+    PAGE_SIZE.load(Ordering::Relaxed)
+}
@@ -0,0 +1,53 @@
+use ctypes::{c_char, c_int, c_ulong};
+use ffi::CStr;
+use io;
+use linux;
+use super::cvt;
+
+fn prctl(option: c_int, arg2: c_ulong) -> io::Result<usize> {
+    cvt(unsafe { linux::prctl(option, arg2, 0, 0, 0) })
+}
+
+// `name` is truncated to 15 bytes by the kernel
+pub fn set_name(name: &CStr) -> io::Result<()> {
+    prctl(linux::PR_SET_NAME, name.as_ptr() as c_ulong).map(|_| ())
+}
+
+// The name including its NUL terminator
+pub fn get_name(buf: &mut [u8; 16]) -> io::Result<()> {
+    prctl(linux::PR_GET_NAME, buf.as_mut_ptr() as *mut c_char as c_ulong)
+        .map(|_| ())
+}
+
+pub fn set_pdeathsig(signal: c_int) -> io::Result<()> {
+    prctl(linux::PR_SET_PDEATHSIG, signal as c_ulong).map(|_| ())
+}
+
+pub fn get_pdeathsig() -> io::Result<c_int> {
+    let mut signal: c_int = 0;
+    prctl(linux::PR_GET_PDEATHSIG, &mut signal as *mut c_int as c_ulong)?;
+    Ok(signal)
+}
+
+pub fn set_flag(option: c_int, on: bool) -> io::Result<()> {
+    prctl(option, on as c_ulong).map(|_| ())
+}
+
+// For the options that return the flag
+pub fn get_flag(option: c_int) -> io::Result<bool> {
+    prctl(option, 0).map(|ret| ret != 0)
+}
+
+pub fn get_child_subreaper() -> io::Result<bool> {
+    let mut on: c_int = 0;
+    prctl(linux::PR_GET_CHILD_SUBREAPER, &mut on as *mut c_int as c_ulong)?;
+    Ok(on != 0)
+}
+
+pub fn set_timerslack(nsecs: c_ulong) -> io::Result<()> {
+    prctl(linux::PR_SET_TIMERSLACK, nsecs).map(|_| ())
+}
+
+pub fn get_timerslack() -> io::Result<c_ulong> {
+    prctl(linux::PR_GET_TIMERSLACK, 0).map(|nsecs| nsecs as c_ulong)
+}
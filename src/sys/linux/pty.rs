@@ -0,0 +1,58 @@
+use ctypes::{c_char, c_int, c_uint, c_ulong};
+use ffi::CString;
+use io;
+use linux;
+use path::PathBuf;
+use sys::errno;
+use sys::fd::FileDesc;
+use super::{cvt, cvt_r};
+
+const FLAGS: c_int = linux::O_RDWR | linux::O_NOCTTY | linux::O_CLOEXEC;
+
+// Opens a new master and its slave, like glibc's `posix_openpt`, `grantpt`,
+// `unlockpt` and opening `ptsname`. With devpts the slave already belongs to
+// us, so there is nothing for `grantpt` to do.
+pub fn openpty() -> io::Result<(FileDesc, FileDesc)> {
+    let master = cvt_r(|| unsafe {
+        linux::open(b"/dev/ptmx\0".as_ptr() as *const c_char, FLAGS, 0)
+    })?;
+    let master = FileDesc::new(master as c_int);
+
+    let unlock: c_int = 0;
+    cvt(unsafe {
+        linux::ioctl(master.raw(),
+                     linux::TIOCSPTLCK,
+                     &unlock as *const c_int as c_ulong)
+    })?;
+
+    // `TIOCGPTPEER` opens the slave without a path lookup, which can't be
+    // fooled by another devpts mounted over /dev/pts. It's missing before
+    // Linux 4.13, and emulators may not know it at all.
+    let slave = match cvt(unsafe {
+        linux::ioctl(master.raw(), linux::TIOCGPTPEER, FLAGS as c_ulong)
+    }) {
+        Ok(fd) => fd,
+        Err(ref e) if e.raw_os_error() == Some(errno::EINVAL) ||
+                      e.raw_os_error() == Some(errno::ENOTTY) ||
+                      e.raw_os_error() == Some(errno::ENOSYS) => {
+            let path = CString::new(format!("/dev/pts/{}", ptn(master.raw())?))?;
+            cvt_r(|| unsafe { linux::open(path.as_ptr(), FLAGS, 0) })?
+        }
+        Err(e) => return Err(e),
+    };
+    Ok((master, FileDesc::new(slave as c_int)))
+}
+
+fn ptn(master: c_int) -> io::Result<c_uint> {
+    let mut n: c_uint = 0;
+    cvt(unsafe {
+        linux::ioctl(master,
+                     linux::TIOCGPTN,
+                     &mut n as *mut c_uint as c_ulong)
+    })?;
+    Ok(n)
+}
+
+pub fn ptsname(master: c_int) -> io::Result<PathBuf> {
+    Ok(PathBuf::from(format!("/dev/pts/{}", ptn(master)?)))
+}
@@ -0,0 +1,32 @@
+use ctypes::{c_int, c_uint};
+use io;
+use linux::{self, pid_t, rlimit64, rusage};
+use mem;
+use ptr;
+use super::cvt;
+
+// `None` stands for `RLIM64_INFINITY`
+pub fn rlimit(soft: Option<u64>, hard: Option<u64>) -> rlimit64 {
+    rlimit64 {
+        rlim_cur: soft.unwrap_or(linux::RLIM64_INFINITY),
+        rlim_max: hard.unwrap_or(linux::RLIM64_INFINITY),
+    }
+}
+
+pub fn finite(value: u64) -> Option<u64> {
+    if value == linux::RLIM64_INFINITY { None } else { Some(value) }
+}
+
+pub fn prlimit(pid: pid_t, resource: c_uint, new: Option<&rlimit64>)
+               -> io::Result<rlimit64> {
+    let mut old: rlimit64 = unsafe { mem::zeroed() };
+    let new = new.map_or(ptr::null(), |new| new as *const rlimit64);
+    cvt(unsafe { linux::prlimit64(pid, resource, new, &mut old) })?;
+    Ok(old)
+}
+
+pub fn getrusage(who: c_int) -> io::Result<rusage> {
+    let mut ru: rusage = unsafe { mem::zeroed() };
+    cvt(unsafe { linux::getrusage(who, &mut ru) })?;
+    Ok(ru)
+}
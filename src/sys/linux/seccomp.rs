@@ -0,0 +1,77 @@
+use ctypes::c_ushort;
+use io::{self, Error};
+use linux::{self, sock_filter, sock_fprog};
+use sys::errno;
+use sys::prctl;
+use super::cvt;
+
+// Offsets into `seccomp_data`
+pub const NR: u32 = 0;
+pub const ARCH: u32 = 4;
+
+// The offsets of the low and high halves of syscall argument `index`, which
+// `seccomp_data` stores as native endian `u64`s
+#[cfg(target_endian = "little")]
+pub fn arg(index: u32) -> (u32, u32) {
+    let offset = 16 + 8 * index;
+    (offset, offset + 4)
+}
+
+#[cfg(target_endian = "big")]
+pub fn arg(index: u32) -> (u32, u32) {
+    let offset = 16 + 8 * index;
+    (offset + 4, offset)
+}
+
+pub fn stmt(code: u16, k: u32) -> sock_filter {
+    jump(code, k, 0, 0)
+}
+
+pub fn jump(code: u16, k: u32, jt: u8, jf: u8) -> sock_filter {
+    sock_filter {
+        code: code,
+        jt: jt,
+        jf: jf,
+        k: k,
+    }
+}
+
+// Kills the process on syscalls made through another ABI than ours, like
+// i386 syscalls on x86_64, as their numbers mean different syscalls
+pub fn prologue(filter: &mut Vec<sock_filter>) {
+    filter.push(stmt(linux::BPF_LD | linux::BPF_W | linux::BPF_ABS, ARCH));
+    filter.push(jump(linux::BPF_JMP | linux::BPF_JEQ | linux::BPF_K,
+                     linux::SECCOMP_ARCH_NATIVE,
+                     1,
+                     0));
+    filter.push(stmt(linux::BPF_RET | linux::BPF_K,
+                     linux::SECCOMP_RET_KILL_PROCESS));
+
+    // x32 syscalls share the architecture of x86_64 but have this bit set
+    #[cfg(target_arch = "x86_64")]
+    {
+        filter.push(stmt(linux::BPF_LD | linux::BPF_W | linux::BPF_ABS, NR));
+        filter.push(jump(linux::BPF_JMP | linux::BPF_JGE | linux::BPF_K,
+                         linux::__X32_SYSCALL_BIT,
+                         0,
+                         1));
+        filter.push(stmt(linux::BPF_RET | linux::BPF_K,
+                         linux::SECCOMP_RET_KILL_PROCESS));
+    }
+}
+
+pub fn install(filter: &[sock_filter]) -> io::Result<()> {
+    if filter.len() > c_ushort::max_value() as usize {
+        return Err(Error::from_raw_os_error(errno::EINVAL));
+    }
+    // Lets unprivileged processes install filters, which could otherwise
+    // confuse set-user-ID programs into misbehaving
+    prctl::set_flag(linux::PR_SET_NO_NEW_PRIVS, true)?;
+    let prog = sock_fprog {
+        len: filter.len() as c_ushort,
+        filter: filter.as_ptr(),
+    };
+    cvt(unsafe {
+        linux::seccomp(linux::SECCOMP_SET_MODE_FILTER, 0, &prog)
+    }).map(|_| ())
+}
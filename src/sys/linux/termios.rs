@@ -0,0 +1,56 @@
+use ctypes::{c_int, c_uint, c_ulong};
+use io;
+use linux::{self, termios, winsize};
+use mem;
+use super::{cvt, cvt_r};
+
+pub fn tcgetattr(fd: c_int) -> io::Result<termios> {
+    let mut t: termios = unsafe { mem::zeroed() };
+    cvt(unsafe {
+        linux::ioctl(fd, linux::TCGETS, &mut t as *mut termios as c_ulong)
+    })?;
+    Ok(t)
+}
+
+// `cmd` is one of `TCSETS`, `TCSETSW` and `TCSETSF`, the latter two wait for
+// pending output to drain and can be interrupted
+pub fn tcsetattr(fd: c_int, cmd: c_uint, t: &termios) -> io::Result<()> {
+    cvt_r(|| unsafe {
+        linux::ioctl(fd, cmd, t as *const termios as c_ulong)
+    })?;
+    Ok(())
+}
+
+pub fn is_terminal(fd: c_int) -> bool {
+    tcgetattr(fd).is_ok()
+}
+
+pub fn window_size(fd: c_int) -> io::Result<winsize> {
+    let mut ws: winsize = unsafe { mem::zeroed() };
+    cvt(unsafe {
+        linux::ioctl(fd, linux::TIOCGWINSZ, &mut ws as *mut winsize as c_ulong)
+    })?;
+    Ok(ws)
+}
+
+pub fn set_window_size(fd: c_int, ws: &winsize) -> io::Result<()> {
+    cvt(unsafe {
+        linux::ioctl(fd, linux::TIOCSWINSZ, ws as *const winsize as c_ulong)
+    })?;
+    Ok(())
+}
+
+// Like glibc's `cfmakeraw`: no line editing, signals or translation, and
+// reads return as soon as a byte is available
+pub fn make_raw(t: &mut termios) {
+    t.c_iflag &= !(linux::IGNBRK | linux::BRKINT | linux::PARMRK |
+                   linux::ISTRIP | linux::INLCR | linux::IGNCR |
+                   linux::ICRNL | linux::IXON);
+    t.c_oflag &= !linux::OPOST;
+    t.c_lflag &= !(linux::ECHO | linux::ECHONL | linux::ICANON |
+                   linux::ISIG | linux::IEXTEN);
+    t.c_cflag &= !(linux::CSIZE | linux::PARENB);
+    t.c_cflag |= linux::CS8;
+    t.c_cc[linux::VMIN] = 1;
+    t.c_cc[linux::VTIME] = 0;
+}
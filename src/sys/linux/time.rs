@@ -107,6 +107,7 @@ impl Ord for Timespec {
 mod inner {
     use fmt;
     use linux;
+    use sys_common::AsInner;
     use time::Duration;
 
     use super::Timespec;
@@ -150,6 +151,10 @@ mod inner {
         }
     }
 
+    impl AsInner<linux::timespec> for Instant {
+        fn as_inner(&self) -> &linux::timespec { &self.t.t }
+    }
+
     impl fmt::Debug for Instant {
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
             f.debug_struct("Instant")
@@ -184,6 +189,10 @@ mod inner {
         }
     }
 
+    impl AsInner<linux::timespec> for SystemTime {
+        fn as_inner(&self) -> &linux::timespec { &self.t.t }
+    }
+
     impl fmt::Debug for SystemTime {
         fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
             f.debug_struct("SystemTime")
@@ -0,0 +1,77 @@
+use cmp;
+use ctypes::{c_int, c_long};
+use io;
+use linux::{self, clockid_t, itimerspec, time_t, timespec};
+use mem;
+use ptr;
+use slice;
+use sys::fd::FileDesc;
+use sys::FromInner;
+use time::Duration;
+use super::cvt;
+
+pub struct TimerFd(FileDesc);
+
+impl TimerFd {
+    pub fn new(clock: clockid_t) -> io::Result<TimerFd> {
+        let fd = cvt(unsafe {
+            linux::timerfd_create(clock, linux::TFD_CLOEXEC)
+        })?;
+        Ok(TimerFd(FileDesc::new(fd as c_int)))
+    }
+
+    pub fn settime(&self, abstime: bool, value: timespec, interval: timespec)
+                   -> io::Result<()> {
+        let flags = if abstime { linux::TFD_TIMER_ABSTIME } else { 0 };
+        let new = itimerspec { it_interval: interval, it_value: value };
+        cvt(unsafe {
+            linux::timerfd_settime(self.0.raw(), flags, &new, ptr::null_mut())
+        })?;
+        Ok(())
+    }
+
+    pub fn gettime(&self) -> io::Result<itimerspec> {
+        let mut cur: itimerspec = unsafe { mem::zeroed() };
+        cvt(unsafe { linux::timerfd_gettime(self.0.raw(), &mut cur) })?;
+        Ok(cur)
+    }
+
+    pub fn read(&self) -> io::Result<u64> {
+        let mut expirations = 0u64;
+        let buf = unsafe {
+            slice::from_raw_parts_mut(&mut expirations as *mut u64 as *mut u8,
+                                      mem::size_of::<u64>())
+        };
+        // A timerfd always transfers all 8 bytes or fails
+        self.0.read(buf)?;
+        Ok(expirations)
+    }
+
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        self.0.set_nonblocking(nonblocking)
+    }
+
+    pub fn fd(&self) -> &FileDesc { &self.0 }
+
+    pub fn into_fd(self) -> FileDesc { self.0 }
+}
+
+impl FromInner<c_int> for TimerFd {
+    fn from_inner(fd: c_int) -> TimerFd {
+        TimerFd(FileDesc::new(fd))
+    }
+}
+
+// Saturates at the largest `time_t`, which is far enough in the future for
+// a timer on 32-bit too.
+pub fn to_timespec(d: Duration) -> timespec {
+    let secs = cmp::min(d.as_secs(), time_t::max_value() as u64);
+    timespec {
+        tv_sec: secs as time_t,
+        tv_nsec: d.subsec_nanos() as c_long,
+    }
+}
+
+pub fn from_timespec(t: timespec) -> Duration {
+    Duration::new(t.tv_sec as u64, t.tv_nsec as u32)
+}
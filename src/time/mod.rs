@@ -26,7 +26,7 @@ use error::Error;
 use fmt;
 use ops::{Add, Sub, AddAssign, SubAssign};
 use sys::time;
-use sys_common::FromInner;
+use sys_common::{AsInner, FromInner};
 
 #[stable(feature = "time", since = "1.3.0")]
 pub use self::duration::Duration;
@@ -331,12 +331,20 @@ impl fmt::Display for SystemTimeError {
     }
 }
 
+impl AsInner<time::Instant> for Instant {
+    fn as_inner(&self) -> &time::Instant { &self.0 }
+}
+
 impl FromInner<time::SystemTime> for SystemTime {
     fn from_inner(time: time::SystemTime) -> SystemTime {
         SystemTime(time)
     }
 }
 
+impl AsInner<time::SystemTime> for SystemTime {
+    fn as_inner(&self) -> &time::SystemTime { &self.0 }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{Instant, SystemTime, Duration, UNIX_EPOCH};